@@ -1,3 +1,23 @@
+use std::{env, fs, path::Path};
+
 pub fn main() {
     println!("cargo:rerun-if-changed=locales");
+    println!("cargo:rerun-if-changed=templates");
+
+    // every process template in the templates directory is compiled in
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("templates");
+    let mut templates = fs::read_dir(&dir)
+        .expect("templates directory")
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect::<Vec<_>>();
+    templates.sort();
+
+    let entries = templates
+        .iter()
+        .map(|path| format!("    include_str!({:?}),\n", path.display().to_string()))
+        .collect::<String>();
+
+    let out = Path::new(&env::var("OUT_DIR").unwrap()).join("templates.rs");
+    fs::write(out, format!("const TEMPLATES: &[&str] = &[\n{entries}];\n")).unwrap();
 }
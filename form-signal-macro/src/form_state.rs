@@ -12,6 +12,8 @@ pub fn make_form_state_fields(fields: &Fields) -> Fields {
             parse_quote! { leptos::RwSignal<#ty> }
         } else if is_iterable(attrs.as_slice()) {
             form_state_generic(&ty)
+        } else if is_keyed(attrs.as_slice()) {
+            form_state_keyed(&ty)
         } else {
             parse_quote! { form_signal::FormState<#ty> }
         };
@@ -42,6 +44,57 @@ pub fn is_iterable(attrs: &[Attribute]) -> bool {
     })
 }
 
+pub fn is_keyed(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|a| {
+        a.path()
+            .get_ident()
+            .map(|i| i.to_string().as_str() == "keyed")
+            .unwrap_or_default()
+    })
+}
+
+/// turns `Vec<(Uuid, T)>` into `Vec<FormState<T>>`
+pub fn form_state_keyed(ty: &Type) -> Type {
+    match ty {
+        Type::Path(p) => {
+            let mut p = p.clone();
+            if let Some(s) = p.path.segments.last_mut() {
+                let syn::PathArguments::AngleBracketed(args) = &mut s.arguments else {
+                    proc_macro_error::emit_error!(
+                        s.span(),
+                        "keyed fields must be lists of `(Uuid, T)` tuples"
+                    );
+                    return ty.clone();
+                };
+                args.args.iter_mut().for_each(|a| {
+                    let replace: Option<Type> = match &*a {
+                        syn::GenericArgument::Type(Type::Tuple(t)) if t.elems.len() == 2 => {
+                            let value_ty = t.elems.last().unwrap();
+                            Some(parse_quote! { form_signal::FormState<#value_ty> })
+                        }
+                        _ => {
+                            proc_macro_error::emit_error!(
+                                a.span(),
+                                "keyed fields must hold `(Uuid, T)` tuples"
+                            );
+                            None
+                        }
+                    };
+                    if let Some(replace) = replace {
+                        *a = syn::GenericArgument::Type(replace);
+                    }
+                });
+            }
+
+            Type::Path(p)
+        }
+        _ => {
+            proc_macro_error::emit_error!(ty.span(), "keyed field types must be Path");
+            ty.clone()
+        }
+    }
+}
+
 pub fn form_state_generic(ty: &Type) -> Type {
     match ty {
         Type::Path(p) => {
@@ -1,8 +1,82 @@
 use proc_macro2::{Span, TokenStream};
-use quote::{quote, ToTokens};
-use syn::{Fields, Generics, Ident, LitInt};
+use quote::{format_ident, quote, ToTokens};
+use syn::{spanned::Spanned, Field, Fields, Generics, Ident, LitInt, Type};
 
-use super::{form_state_type, is_iterable, is_nested};
+use super::{field_validators, form_state_type, is_iterable, is_keyed, is_nested, with_validators};
+
+/// name of the constructor of entries of an iterable or keyed field, e.g. `value_entry`
+pub fn entry_fn(i: usize, field: &Field) -> Ident {
+    field
+        .ident
+        .as_ref()
+        .map(|ident| format_ident!("{}_entry", ident))
+        .unwrap_or_else(|| format_ident!("entry_{}", i))
+}
+
+/// `T` of `Vec<T>`, or the key and value types of `Vec<(K, T)>`
+fn entry_types(ty: &Type) -> Option<(Option<Type>, Type)> {
+    let Type::Path(p) = ty else {
+        return None;
+    };
+    let syn::PathArguments::AngleBracketed(args) = &p.path.segments.last()?.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        syn::GenericArgument::Type(Type::Tuple(t)) if t.elems.len() == 2 => {
+            Some((t.elems.first().cloned(), t.elems.last().cloned().unwrap()))
+        }
+        syn::GenericArgument::Type(ty) => Some((None, ty.clone())),
+        _ => None,
+    }
+}
+
+/// constructors of list entries which carry the validators of the field,
+/// lists gain entries after `From` so every new entry must be made with these
+pub fn make_entries(fields: &Fields, generics: &Generics, struct_name: &Ident) -> TokenStream {
+    let entries = fields.iter().enumerate().filter_map(|(i, field)| {
+        let keyed = is_keyed(field.attrs.as_slice());
+        if !keyed && !is_iterable(field.attrs.as_slice()) {
+            return None;
+        }
+        let name = entry_fn(i, field);
+        let validators = field_validators(field.attrs.as_slice());
+        match entry_types(&field.ty) {
+            Some((Some(key_ty), value_ty)) if keyed => {
+                let state = with_validators(
+                    quote! { form_signal::FormState::with_id(id, value) },
+                    &validators,
+                );
+                Some(quote! {
+                    pub fn #name(id: #key_ty, value: #value_ty) -> form_signal::FormState<#value_ty> {
+                        #state
+                    }
+                })
+            }
+            Some((None, value_ty)) if !keyed => {
+                let state =
+                    with_validators(quote! { form_signal::FormState::new(value) }, &validators);
+                Some(quote! {
+                    pub fn #name(value: #value_ty) -> form_signal::FormState<#value_ty> {
+                        #state
+                    }
+                })
+            }
+            _ => {
+                proc_macro_error::emit_error!(
+                    field.ty.span(),
+                    "expected `Vec<T>` for iterable or `Vec<(Uuid, T)>` for keyed fields"
+                );
+                None
+            }
+        }
+    });
+
+    quote! {
+        impl #generics #struct_name #generics {
+            #(#entries)*
+        }
+    }
+}
 
 pub fn make_impl_from(
     fields: &Fields,
@@ -30,18 +104,29 @@ pub fn make_impl_from(
                     ident,
                 )
             } else if is_iterable(field.attrs.as_slice()) {
+                let entry = entry_fn(i, field);
                 (
                     quote! {
-                        value.#ident.into_iter().map(|v| {
-                            form_signal::FormState::new(v)
-                        }).collect(),
+                        value.#ident.into_iter().map(Self::#entry).collect(),
+                    },
+                    ident,
+                )
+            } else if is_keyed(field.attrs.as_slice()) {
+                let entry = entry_fn(i, field);
+                (
+                    quote! {
+                        value.#ident.into_iter().map(|(id, v)| Self::#entry(id, v)).collect(),
                     },
                     ident,
                 )
             } else {
+                let state = with_validators(
+                    quote! { form_signal::FormState::new(value.#ident) },
+                    &field_validators(field.attrs.as_slice()),
+                );
                 (
                     quote! {
-                        form_signal::FormState::new(value.#ident),
+                        #state,
                     },
                     ident,
                 )
@@ -114,6 +199,18 @@ pub fn make_impl_into(
                     },
                     ident,
                 )
+            } else if is_keyed(field.attrs.as_slice()) {
+                (
+                    quote! {
+                        self.#ident
+                            .iter()
+                            .map(|s| {
+                                (s.id, leptos::SignalGetUntracked::get_untracked(s))
+                            })
+                            .collect(),
+                    },
+                    ident,
+                )
             } else {
                 (
                     quote! {
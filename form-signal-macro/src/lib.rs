@@ -2,6 +2,7 @@ mod form_state;
 mod form_state_self;
 mod from_into;
 mod signal_get;
+mod validate;
 
 use form_state::*;
 use form_state_self::*;
@@ -10,6 +11,7 @@ use proc_macro2::Span;
 use quote::quote;
 use signal_get::*;
 use syn::{parse_macro_input, DataStruct, DeriveInput};
+use validate::*;
 
 /// Given a `struct StructName { value: String }`
 /// generates a corresponding `struct StructNameFormState { value: FormState<String> }`
@@ -22,7 +24,19 @@ use syn::{parse_macro_input, DataStruct, DeriveInput};
 ///
 /// use `#[iterable]` field attribute for fields with iterable values
 /// only iterables with single generic are supported aka `Vec<T>`
-#[proc_macro_derive(FormState, attributes(nested, iterable))]
+///
+/// use `#[keyed]` field attribute for iterables of `(Uuid, T)`
+/// turning them into `Vec<FormState<T>>` which keep the ids of entries
+///
+/// use `#[validate(required, length(min = 1, max = 10), email)]` field attribute
+/// to check the values of plain, iterable or keyed fields, the derived `FormState`
+/// exposes `is_valid()` which is true when the values of all fields are valid
+///
+/// entries of iterable and keyed fields must be made with the derived
+/// `{field}_entry` constructors to carry the validators of the field,
+/// blank entries of lists, whitespace only text included, are skipped and `required`
+/// asks for one non blank entry
+#[proc_macro_derive(FormState, attributes(nested, iterable, keyed, validate))]
 pub fn derive_form_state(item: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let DeriveInput {
         vis,
@@ -90,6 +104,10 @@ pub fn derive_form_state(item: proc_macro::TokenStream) -> proc_macro::TokenStre
 
     let impl_self = make_self(&ident, &generics, &struct_name);
 
+    let impl_entries = make_entries(&fields, &generics, &struct_name);
+
+    let impl_is_valid = make_is_valid(&fields, &generics, &struct_name);
+
     let expanded = quote! {
         #[derive(Clone, Debug, PartialEq, Eq)]
         #vis #struct_token #struct_name #generics #form_state_fields #semi_token
@@ -103,6 +121,10 @@ pub fn derive_form_state(item: proc_macro::TokenStream) -> proc_macro::TokenStre
         #impl_signal_get_untracked
 
         #impl_self
+
+        #impl_entries
+
+        #impl_is_valid
     };
 
     proc_macro::TokenStream::from(expanded)
@@ -2,7 +2,7 @@ use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
 use syn::{Fields, Generics, Ident, LitInt};
 
-use super::{is_iterable, is_nested};
+use super::{is_iterable, is_keyed, is_nested};
 
 pub fn make_signal_get(
     fields: &Fields,
@@ -59,6 +59,26 @@ pub fn make_signal_get(
                     ),
                     ident,
                 )
+            } else if is_keyed(field.attrs.as_slice()) {
+                let ty = field.ty.clone();
+
+                (
+                    (
+                        quote! {
+                            self.#ident.iter().map(|v| {
+                                (v.id, #trait_path::#getter(v))
+                            }).collect::<#ty>(),
+                        },
+                        quote! {
+                            {
+                                self.#ident
+                                    .iter()
+                                    .any(|v| #trait_path::#try_getter(v).is_some())
+                            },
+                        },
+                    ),
+                    ident,
+                )
             } else {
                 (
                     (
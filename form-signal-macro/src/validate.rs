@@ -0,0 +1,128 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, ToTokens};
+use syn::{
+    punctuated::Punctuated, spanned::Spanned, Attribute, Fields, Generics, Ident, LitInt, Meta,
+    Token,
+};
+
+use super::{is_iterable, is_keyed, is_nested};
+
+/// validators of `#[validate(required, length(min = 1, max = 10), email)]` attributes
+pub fn field_validators(attrs: &[Attribute]) -> Vec<TokenStream> {
+    let mut validators = vec![];
+
+    attrs
+        .iter()
+        .filter(|a| {
+            a.path()
+                .get_ident()
+                .map(|i| i.to_string().as_str() == "validate")
+                .unwrap_or_default()
+        })
+        .for_each(|a| {
+            let parsed = a.parse_nested_meta(|meta| {
+                if meta.path.is_ident("required") {
+                    validators.push(quote! { form_signal::required() });
+                    Ok(())
+                } else if meta.path.is_ident("email") {
+                    validators.push(quote! { form_signal::email() });
+                    Ok(())
+                } else if meta.path.is_ident("length") {
+                    let (mut min, mut max) = (quote! { None }, quote! { None });
+                    meta.parse_nested_meta(|bound| {
+                        let value: LitInt = bound.value()?.parse()?;
+                        if bound.path.is_ident("min") {
+                            min = quote! { Some(#value) };
+                            Ok(())
+                        } else if bound.path.is_ident("max") {
+                            max = quote! { Some(#value) };
+                            Ok(())
+                        } else {
+                            Err(bound.error("expected `min` or `max`"))
+                        }
+                    })?;
+                    validators.push(quote! { form_signal::length(#min, #max) });
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `required`, `length` or `email`"))
+                }
+            });
+            if let Err(e) = parsed {
+                proc_macro_error::emit_error!(e.span(), "{}", e);
+            }
+        });
+
+    validators
+}
+
+/// whether a `#[validate(required)]` attribute is present
+pub fn is_required(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|a| a.path().is_ident("validate"))
+        .filter_map(|a| {
+            a.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                .ok()
+        })
+        .flatten()
+        .any(|meta| meta.path().is_ident("required"))
+}
+
+/// adds the validators of the field to a form state expression
+pub fn with_validators(expression: TokenStream, validators: &[TokenStream]) -> TokenStream {
+    quote! {
+        #expression #(.with_validator(#validators))*
+    }
+}
+
+/// `check` and `is_valid` of the derived form state, valid when all of its fields are
+pub fn make_is_valid(fields: &Fields, generics: &Generics, struct_name: &Ident) -> TokenStream {
+    let checks = fields.iter().enumerate().map(|(i, field)| {
+        let ident =
+            field.ident.clone().map(|i| i.to_token_stream()).unwrap_or(
+                LitInt::new(format!("{i}").as_str(), Span::mixed_site()).to_token_stream(),
+            );
+        if is_nested(field.attrs.as_slice()) {
+            if !field_validators(field.attrs.as_slice()).is_empty() {
+                proc_macro_error::emit_error!(
+                    field.span(),
+                    "validate the fields of the nested form instead"
+                );
+            }
+            quote! {
+                leptos::SignalWith::with(&self.#ident, |n| n.check())
+            }
+        } else if is_iterable(field.attrs.as_slice()) || is_keyed(field.attrs.as_slice()) {
+            // lists keep a blank entry to type into
+            let required = is_required(field.attrs.as_slice());
+            quote! {
+                {
+                    let entries = self.#ident.iter().filter(|s| !s.is_blank());
+                    let (count, valid) = entries.fold((0, true), |(count, valid), s| {
+                        (count + 1, valid && s.check())
+                    });
+                    valid && (!#required || count > 0)
+                }
+            }
+        } else {
+            quote! {
+                self.#ident.check()
+            }
+        }
+    });
+
+    quote! {
+        impl #generics #struct_name #generics {
+            /// whether every field is valid, tracks the values and the validators
+            pub fn check(&self) -> bool {
+                true #(&& #checks)*
+            }
+
+            /// whether every field is valid
+            pub fn is_valid(&self) -> leptos::Signal<bool> {
+                let state = self.clone();
+                leptos::Signal::derive(move || state.check())
+            }
+        }
+    }
+}
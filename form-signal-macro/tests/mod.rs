@@ -100,3 +100,196 @@ fn nested_generics() {
 
     assert_eq!(d, dd);
 }
+
+#[test]
+fn keyed_test() {
+    _ = leptos::create_runtime();
+
+    #[derive(FormState, PartialEq, Eq, Debug, Clone, Default)]
+    struct TestStruct {
+        #[keyed]
+        value: Vec<(uuid::Uuid, String)>,
+    }
+
+    let d = TestStruct {
+        value: vec![
+            (uuid::Uuid::new_v4(), "test".to_string()),
+            (uuid::Uuid::new_v4(), "test 1".to_string()),
+        ],
+    };
+
+    let dd = TestStructFormState::from(d.clone());
+
+    assert_eq!(dd.value[0].id, d.value[0].0);
+    assert_eq!(dd.value[1].id, d.value[1].0);
+
+    let dd: TestStruct = (&dd).into();
+
+    assert_eq!(d, dd);
+}
+
+#[test]
+fn validate_test() {
+    use leptos::{SignalGet, SignalSet};
+
+    _ = leptos::create_runtime();
+
+    #[derive(FormState, PartialEq, Eq, Debug, Clone, Default)]
+    struct TestStruct {
+        #[validate(required, length(min = 2, max = 8))]
+        name: String,
+        #[validate(email)]
+        email: String,
+        note: String,
+    }
+
+    let d = TestStruct {
+        name: "test".to_string(),
+        email: String::default(),
+        note: String::default(),
+    };
+
+    let dd = TestStructFormState::from(d.clone());
+    let is_valid = dd.is_valid();
+
+    assert!(is_valid.get());
+
+    dd.name.set(String::default());
+    assert_eq!(
+        dd.name.errors().get(),
+        vec![form_signal::ValidationError::Required]
+    );
+    assert!(!is_valid.get());
+
+    dd.name.set("t".to_string());
+    assert_eq!(
+        dd.name.errors().get(),
+        vec![form_signal::ValidationError::TooShort(2)]
+    );
+
+    dd.name.set("test test".to_string());
+    assert_eq!(
+        dd.name.errors().get(),
+        vec![form_signal::ValidationError::TooLong(8)]
+    );
+
+    dd.name.set("test".to_string());
+    dd.email.set("test".to_string());
+    assert_eq!(
+        dd.email.errors().get(),
+        vec![form_signal::ValidationError::Email]
+    );
+    assert!(!is_valid.get());
+
+    dd.email.set("test@example.com".to_string());
+    assert!(is_valid.get());
+
+    let dd: TestStruct = (&dd).into();
+
+    assert_eq!(dd.email, "test@example.com");
+}
+
+#[test]
+fn nested_validate_test() {
+    use leptos::{SignalGet, SignalGetUntracked, SignalSet};
+
+    _ = leptos::create_runtime();
+
+    #[derive(FormState, PartialEq, Eq, Debug, Clone, Default)]
+    struct TestNestedStruct {
+        #[iterable]
+        #[validate(required)]
+        value: Vec<String>,
+        #[keyed]
+        #[validate(length(max = 4))]
+        keyed: Vec<(uuid::Uuid, String)>,
+    }
+
+    #[derive(FormState, PartialEq, Eq, Debug, Clone, Default)]
+    struct TestStruct {
+        #[nested]
+        value: TestNestedStruct,
+    }
+
+    let d = TestStruct {
+        value: TestNestedStruct {
+            value: vec!["test".to_string(), "test 1".to_string()],
+            keyed: vec![(uuid::Uuid::new_v4(), "test".to_string())],
+        },
+    };
+
+    let dd = TestStructFormState::from(d.clone());
+    let is_valid = dd.is_valid();
+
+    assert!(is_valid.get());
+
+    let nested = dd.value.get_untracked();
+
+    // whitespace only entries are blank like empty ones
+    nested.value[1].set(" ".to_string());
+    assert!(is_valid.get());
+
+    nested.value[0].set(String::default());
+    assert!(!is_valid.get());
+
+    nested.value[0].set("test".to_string());
+    nested.value[1].set("test 1".to_string());
+    assert!(is_valid.get());
+
+    nested.keyed[0].set("test 1".to_string());
+    assert_eq!(
+        nested.keyed[0].errors().get(),
+        vec![form_signal::ValidationError::TooLong(4)]
+    );
+    assert!(!is_valid.get());
+}
+
+#[test]
+fn list_entries_validate_test() {
+    use leptos::{SignalGet, SignalGetUntracked, SignalSet};
+
+    _ = leptos::create_runtime();
+
+    #[derive(FormState, PartialEq, Eq, Debug, Clone, Default)]
+    struct TestStruct {
+        #[iterable]
+        #[validate(required, length(max = 4))]
+        value: Vec<String>,
+        #[keyed]
+        #[validate(length(max = 4))]
+        keyed: Vec<(uuid::Uuid, String)>,
+    }
+
+    let dd = TestStructFormState::from(TestStruct {
+        value: vec![String::default()],
+        keyed: vec![],
+    });
+    let is_valid = dd.is_valid();
+
+    // only the blank entry to type into
+    assert!(!is_valid.get());
+
+    dd.value[0].set("test".to_string());
+    assert!(is_valid.get());
+
+    let mut dd = dd;
+    dd.value.push(TestStructFormState::value_entry(String::default()));
+    let is_valid = dd.is_valid();
+    assert!(is_valid.get());
+
+    dd.value[1].set("test 1".to_string());
+    assert_eq!(
+        dd.value[1].errors().get(),
+        vec![form_signal::ValidationError::TooLong(4)]
+    );
+    assert!(!is_valid.get());
+
+    let id = uuid::Uuid::new_v4();
+    dd.value[1].set("test".to_string());
+    dd.keyed
+        .push(TestStructFormState::keyed_entry(id, "test 1".to_string()));
+    let is_valid = dd.is_valid();
+    assert_eq!(dd.keyed[0].id, id);
+    assert_eq!(dd.keyed[0].get_untracked(), "test 1");
+    assert!(!is_valid.get());
+}
@@ -1,8 +1,12 @@
-use std::fmt::Debug;
+mod validators;
+
+use std::{any::Any, fmt::Debug};
 
 use leptos::*;
 use uuid::Uuid;
 
+pub use validators::*;
+
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub enum FormStatus {
     #[default]
@@ -20,6 +24,7 @@ where
     pub id: Uuid,
     value: RwSignal<T>,
     status: RwSignal<FormStatus>,
+    validators: RwSignal<Vec<Validator<T>>>,
 }
 
 impl<T> SignalGet for FormState<T>
@@ -103,6 +108,17 @@ where
             id: Uuid::new_v4(),
             status: Default::default(),
             value: RwSignal::new(value),
+            validators: Default::default(),
+        }
+    }
+
+    /// creates a form state which keeps a known id, e.g. of a persisted list entry
+    pub fn with_id(id: Uuid, value: T) -> Self {
+        Self {
+            id,
+            status: Default::default(),
+            value: RwSignal::new(value),
+            validators: Default::default(),
         }
     }
 
@@ -117,6 +133,45 @@ where
     pub fn status(&self) -> FormStatus {
         self.status.get_untracked()
     }
+
+    /// adds a check of the value, see [required], [length] and [email]
+    pub fn with_validator(self, validator: Validator<T>) -> Self {
+        self.validators.update(|v| v.push(validator));
+        self
+    }
+
+    /// errors of the current value, updates with the value and the validators
+    pub fn errors(&self) -> Signal<Vec<ValidationError>> {
+        let (value, validators) = (self.value, self.validators);
+        Signal::derive(move || {
+            validators.with(|validators| {
+                value.with(|value| validators.iter().filter_map(|v| v(value)).collect())
+            })
+        })
+    }
+
+    /// whether the value is the default one or whitespace only text, e.g. the blank entry of a list
+    pub fn is_blank(&self) -> bool {
+        self.value
+            .with(|value| match (value as &dyn Any).downcast_ref::<String>() {
+                Some(text) => text.trim().is_empty(),
+                None => value == &T::default(),
+            })
+    }
+
+    /// whether the current value passes every validator, tracks the value and the validators
+    pub fn check(&self) -> bool {
+        self.validators.with(|validators| {
+            self.value
+                .with(|value| validators.iter().all(|v| v(value).is_none()))
+        })
+    }
+
+    /// whether the current value passes every validator
+    pub fn is_valid(&self) -> Signal<bool> {
+        let state = self.clone();
+        Signal::derive(move || state.check())
+    }
 }
 
 impl<T> Default for FormState<T>
@@ -0,0 +1,74 @@
+use std::{fmt::Display, rc::Rc};
+
+/// reason a value of a form state is invalid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    Required,
+    TooShort(usize),
+    TooLong(usize),
+    Email,
+    Custom(String),
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::Required => write!(f, "required"),
+            ValidationError::TooShort(min) => write!(f, "at least {min} characters"),
+            ValidationError::TooLong(max) => write!(f, "at most {max} characters"),
+            ValidationError::Email => write!(f, "invalid email"),
+            ValidationError::Custom(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+/// check of a value, returns the error of an invalid value
+pub type Validator<T> = Rc<dyn Fn(&T) -> Option<ValidationError>>;
+
+/// value must not be blank
+pub fn required<T: AsRef<str>>() -> Validator<T> {
+    Rc::new(|value: &T| {
+        value
+            .as_ref()
+            .trim()
+            .is_empty()
+            .then_some(ValidationError::Required)
+    })
+}
+
+/// value must have between `min` and `max` characters, blank values are left to [required]
+pub fn length<T: AsRef<str>>(min: Option<usize>, max: Option<usize>) -> Validator<T> {
+    Rc::new(move |value: &T| {
+        let value = value.as_ref().trim();
+        let count = value.chars().count();
+        match (min, max) {
+            _ if value.is_empty() => None,
+            (Some(min), _) if count < min => Some(ValidationError::TooShort(min)),
+            (_, Some(max)) if count > max => Some(ValidationError::TooLong(max)),
+            _ => None,
+        }
+    })
+}
+
+/// value must look like an email address, blank values are left to [required]
+pub fn email<T: AsRef<str>>() -> Validator<T> {
+    Rc::new(|value: &T| {
+        let value = value.as_ref().trim();
+        if value.is_empty() {
+            return None;
+        }
+        let valid = value
+            .split_once('@')
+            .map(|(local, domain)| {
+                !local.is_empty()
+                    && !domain.contains('@')
+                    && domain
+                        .split_once('.')
+                        .is_some_and(|(host, tld)| !host.is_empty() && !tld.is_empty())
+                    && !domain.ends_with('.')
+                    && !value.chars().any(char::is_whitespace)
+            })
+            .unwrap_or_default();
+        (!valid).then_some(ValidationError::Email)
+    })
+}
@@ -0,0 +1,3 @@
+DROP INDEX collab_entry_revision;
+DROP TABLE collab_entry;
+DROP TABLE collab_session;
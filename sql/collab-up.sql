@@ -0,0 +1,19 @@
+CREATE TABLE collab_session (
+    code TEXT PRIMARY KEY,
+    revision INTEGER NOT NULL DEFAULT 0,
+    created_date INTEGER NOT NULL,
+    updated_date INTEGER NOT NULL
+);
+
+CREATE TABLE collab_entry (
+    session TEXT NOT NULL REFERENCES collab_session(code) ON DELETE CASCADE,
+    field TEXT NOT NULL,
+    id BLOB NOT NULL,
+    position INTEGER NOT NULL,
+    value TEXT NOT NULL,
+    deleted INTEGER NOT NULL,
+    revision INTEGER NOT NULL,
+    PRIMARY KEY (session, field, id)
+);
+
+CREATE INDEX collab_entry_revision ON collab_entry(session, revision);
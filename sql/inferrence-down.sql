@@ -0,0 +1,2 @@
+DROP TABLE inferrence_cache;
+DROP TABLE inferrence;
@@ -0,0 +1,29 @@
+CREATE TABLE inferrence (
+    id BLOB PRIMARY KEY,
+    tracking_id BLOB REFERENCES tracking(id),
+    prompt TEXT NOT NULL,
+    response TEXT NOT NULL DEFAULT '',
+    max_tokens INTEGER NOT NULL,
+    generated_tokens INTEGER NOT NULL DEFAULT 0,
+    temperature REAL NOT NULL,
+    done INTEGER NOT NULL DEFAULT 0,
+    created_date INTEGER NOT NULL,
+    updated_date INTEGER NOT NULL
+);
+
+ALTER TABLE inferrence ADD COLUMN cache_key BLOB;
+
+CREATE TABLE inferrence_cache (
+    key BLOB PRIMARY KEY,
+    response TEXT NOT NULL,
+    generated_tokens INTEGER NOT NULL,
+    created_date INTEGER NOT NULL,
+    expires_date INTEGER NOT NULL
+);
+
+ALTER TABLE inferrence ADD COLUMN inquery_option TEXT;
+ALTER TABLE inferrence ADD COLUMN template_version INTEGER;
+ALTER TABLE inferrence ADD COLUMN rating INTEGER;
+ALTER TABLE inferrence ADD COLUMN feedback TEXT;
+ALTER TABLE inferrence ADD COLUMN rated_date INTEGER;
+ALTER TABLE inferrence ADD COLUMN trimmed TEXT;
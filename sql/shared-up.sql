@@ -0,0 +1,7 @@
+CREATE TABLE shared_wk (
+    slug TEXT PRIMARY KEY,
+    wk TEXT NOT NULL,
+    revoke_token BLOB NOT NULL,
+    created_date INTEGER NOT NULL,
+    expires_date INTEGER
+);
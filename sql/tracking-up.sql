@@ -9,3 +9,5 @@ CREATE TABLE tracking (
 );
 
 ALTER TABLE tracking ADD COLUMN restored_session BLOB REFERENCES tracking(id);
+
+ALTER TABLE tracking ADD COLUMN inferrence_flags TEXT;
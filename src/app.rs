@@ -69,6 +69,7 @@ pub fn App() -> impl IntoView {
                         <Route path=":lang/process/download" view=process::WorksheetsDownload/>
                         <Route path=":lang" view=LocalizedRootView>
                             <Route path="" view=LandingView/>
+                            <Route path="process/shared/:slug?" view=SharedWorksheetsView/>
                             <Route path="process" view=ProcessView ssr=SsrMode::PartiallyBlocked>
                                 <Route path="" view=|| view!{
                                     <Redirect path="0" />
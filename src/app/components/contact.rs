@@ -1,3 +1,4 @@
+use form_signal::FormState;
 use leptos::*;
 
 use crate::app::{components::StringInputView, state::ContactFormState};
@@ -7,35 +8,48 @@ pub fn ContactForm(value: Signal<ContactFormState>) -> impl IntoView {
     let contact_name = Signal::derive(move || value.get().name);
     let contact_email = Signal::derive(move || value.get().email);
     let contact_message = Signal::derive(move || value.get().message);
+    // blank fields are left to the required attribute
+    let invalid = |value: Signal<FormState<String>>| {
+        Signal::derive(move || {
+            let value = value.get();
+            !value.is_blank() && !value.check()
+        })
+    };
+    let name_invalid = invalid(contact_name);
+    let email_invalid = invalid(contact_email);
+    let message_invalid = invalid(contact_message);
 
     view! {
-        <label class="block my-2">
+        <label class="block my-2" id="inquire-contact-name">
             <p class="mb-1">{t!("contact.name.label")}</p>
             <StringInputView
                 attr:required=true
                 attr:autocomplete="given-name"
                 attr:name="name"
+                attr:aria-invalid=move || name_invalid.get().to_string()
                 input_type="text"
                 value=contact_name
                 placeholder=t!("contact.name.placeholder").to_string()
             />
         </label>
-        <label class="block my-2">
+        <label class="block my-2" id="inquire-contact-email">
             <p class="mb-1">{t!("contact.email.label")}</p>
             <StringInputView
                 attr:required=true
                 attr:autocomplete="email"
                 attr:name="email"
+                attr:aria-invalid=move || email_invalid.get().to_string()
                 input_type="email"
                 value=contact_email
                 placeholder=t!("contact.email.placeholder").to_string()
             />
         </label>
-        <label class="block my-2">
+        <label class="block my-2" id="inquire-contact-message">
             <p class="mb-1">{t!("contact.message.label")}</p>
             <StringInputView
                 attr:required=true
                 attr:name="message"
+                attr:aria-invalid=move || message_invalid.get().to_string()
                 input_type="textarea"
                 value=contact_message
                 placeholder=t!("contact.message.placeholder").to_string()
@@ -0,0 +1,36 @@
+use chrono::NaiveDate;
+use form_signal::FormState;
+use leptos::*;
+
+use crate::app::state::{format_date, DATE_FORMAT};
+
+#[component]
+pub fn DateInputView(
+    #[prop(into)] value: Signal<FormState<Option<NaiveDate>>>,
+    #[prop(into, optional)] min: MaybeSignal<Option<NaiveDate>>,
+    #[prop(into, optional)] max: MaybeSignal<Option<NaiveDate>>,
+    #[prop(into, optional)] disabled: MaybeSignal<bool>,
+    #[prop(attrs, optional)] attrs: Vec<(&'static str, Attribute)>,
+) -> impl IntoView {
+    let on_input = move |event| {
+        let next = NaiveDate::parse_from_str(event_target_value(&event).as_str(), DATE_FORMAT).ok();
+        let state = value.get();
+        state.update(move |v| *v = next);
+        state.touch();
+    };
+
+    let class = move || {
+        format!("w-full px-4 py-2 rounded border border-slate-400 bg-stone-50 dark:bg-stone-950 text-stone-950 dark:text-stone-50 text-lg focus:outline-purple-400 focus:outline {}", if disabled.get() { "pointer-events-none contrast-50 saturate-50" } else {""})
+    };
+
+    html::input()
+        .attrs(attrs)
+        .attr("type", "date")
+        .attr("id", move || value.get().id.to_string())
+        .attr("class", class)
+        .attr("disabled", disabled)
+        .attr("min", move || format_date(min.get()))
+        .attr("max", move || format_date(max.get()))
+        .prop("value", move || format_date(value.get().get()))
+        .on(ev::input, on_input)
+}
@@ -6,12 +6,15 @@ use leptos_use::{
 };
 use uuid::Uuid;
 
-use crate::app::components::{ButtonView, IconView, StringInputView};
+use crate::app::{
+    components::{ButtonView, IconView, StringInputView, TextValue},
+    state::KeyedEntry,
+};
 
 #[component]
-pub fn ListInputView(
+pub fn ListInputView<T: TextValue>(
     #[prop(into)] input_type: String,
-    #[prop(into)] data: Signal<Vec<FormState<String>>>,
+    #[prop(into)] data: Signal<Vec<FormState<T>>>,
     #[prop(into)] add_value: Callback<(String, Option<usize>), Uuid>,
     #[prop(into)] remove_value: Callback<Uuid>,
     #[prop(into, optional)] drop_target_name: MaybeSignal<String>,
@@ -31,8 +34,13 @@ pub fn ListInputView(
         focused_id.set(Some(next_id));
     };
 
-    let with_placeholder_id =
-        create_memo(move |_| data.get().iter().take(1).find(|v| v.get().is_empty()).map(|v| v.id));
+    let with_placeholder_id = create_memo(move |_| {
+        data.get()
+            .iter()
+            .take(1)
+            .find(|v| v.get().text().is_empty())
+            .map(|v| v.id)
+    });
 
     let element = create_node_ref::<html::Div>();
     #[cfg_attr(feature = "ssr", allow(unused_variables))]
@@ -56,7 +64,7 @@ pub fn ListInputView(
                     data.iter()
                         .enumerate()
                         .find(|(_, d)| d.id == id)
-                        .filter(|(_, d)| d.get().is_empty())
+                        .filter(|(_, d)| d.get().text().is_empty())
                         .map(|(i, d)| (d.id, data.iter().nth(i.saturating_sub(1)).map(|d| d.id)))
                 })
                 .flatten()
@@ -102,10 +110,7 @@ pub fn ListInputView(
                 <Show
                     when={move || data.try_get().unwrap_or_default().len() <= 1}
                 >
-                    <ListDropTarget
-                        item_after=FormState::new(Default::default())
-                        drop_target_name=drop_target_name
-                    />
+                    <ListDropTarget after=Uuid::nil() drop_target_name=drop_target_name/>
                 </Show>
                 <For
                     each=move || data.try_get().unwrap_or_default().into_iter().enumerate()
@@ -124,10 +129,7 @@ pub fn ListInputView(
                         remove_value
                         delete_allowed
                     />
-                    <ListDropTarget
-                        item_after=child.1.clone()
-                        drop_target_name=drop_target_name
-                    />
+                    <ListDropTarget after=child.1.id drop_target_name=drop_target_name/>
                 </For>
             </ol>
             <div class="contents">
@@ -149,7 +151,7 @@ pub fn ListInputView(
 }
 
 #[component]
-fn ListItemView(
+fn ListItemView<T: TextValue>(
     #[prop(into)] input_type: String,
     #[prop(into, optional)] placeholder: MaybeSignal<String>,
     #[prop(into)] with_placeholder_id: Signal<Option<Uuid>>,
@@ -158,7 +160,7 @@ fn ListItemView(
     #[prop(into)] remove_value: Callback<Uuid>,
     #[prop(into)] on_blur: Callback<ev::FocusEvent>,
     #[prop(into)] on_focus: Callback<ev::FocusEvent>,
-    #[prop(into)] item: FormState<String>,
+    #[prop(into)] item: FormState<T>,
     #[prop(into)] delete_allowed: Signal<bool>,
 ) -> impl IntoView {
     let value = Signal::derive(move || item.clone());
@@ -208,8 +210,7 @@ fn ListItemView(
                 let drag_ctx = drag_ctx.clone();
                 move |_| {
                     if let Some(ctx) = drag_ctx.as_ref() {
-                        let value = value.get();
-                        ctx.0.set(Some(value));
+                        ctx.0.set(Some(value.get().id));
                         true
                     } else {
                         false
@@ -221,7 +222,9 @@ fn ListItemView(
                 move |_| {
                     if let Some(ctx) = drag_ctx.as_ref() {
                         if let Some((insert_after, drop_target)) = ctx.1.get() {
-                            ctx.2.call((value.get(), drop_target, insert_after));
+                            let value = value.get();
+                            let entry = (value.id, value.get_untracked().text());
+                            ctx.2.call((entry, drop_target, insert_after));
                         } else {
                             log::debug!("drop restore position")
                         }
@@ -304,15 +307,88 @@ fn ListItemView(
     }
 }
 
+/// read only entry which can be dragged onto the drop targets of a `DragListCtx`
+#[component]
+pub fn DragChipView(#[prop(into)] item: FormState<String>) -> impl IntoView {
+    let dragable_ref = create_node_ref::<html::Li>();
+    let drag_ctx = use_context::<DragListCtx>();
+
+    let UseDraggableReturn {
+        is_dragging, style, ..
+    } = use_draggable_with_options(
+        dragable_ref,
+        UseDraggableOptions::default()
+            .on_start({
+                let drag_ctx = drag_ctx.clone();
+                let item = item.clone();
+                move |_| {
+                    if let Some(ctx) = drag_ctx.as_ref() {
+                        ctx.0.set(Some(item.id));
+                        true
+                    } else {
+                        false
+                    }
+                }
+            })
+            .on_end({
+                let drag_ctx = drag_ctx.clone();
+                let item = item.clone();
+                move |_| {
+                    if let Some(ctx) = drag_ctx.as_ref() {
+                        if let Some((insert_after, drop_target)) = ctx.1.get() {
+                            let entry = (item.id, item.get_untracked());
+                            ctx.2.call((entry, drop_target, insert_after));
+                        }
+                        ctx.0.set(None);
+                    }
+                }
+            }),
+    );
+
+    let style = Signal::derive(move || {
+        let style = style.get();
+        if is_dragging.get() {
+            Some(format!("position: fixed; {style}"))
+        } else {
+            None
+        }
+    });
+
+    let class = Signal::derive(move || {
+        format!(
+            "flex items-center gap-2 mb-2 px-3 py-2 rounded border border-slate-400 bg-stone-50 dark:bg-stone-950 text-stone-950 dark:text-stone-50 text-left cursor-move {}",
+            if is_dragging.get() {
+                "shadow-lg scale-110"
+            } else {
+                ""
+            }
+        )
+    });
+
+    view! {
+        <li
+            class=class
+            style=style
+            title={t!("util.reorder")}
+            node_ref={dragable_ref}
+        >
+            <IconView icon="Drag"/>
+            <span class="whitespace-pre-line">{move || item.get()}</span>
+        </li>
+    }
+}
+
+/// the dragged entry, the drop target under it and a callback with the id and text
+/// of the dropped entry, the name of the target and the id of the entry to insert after
 #[derive(Clone, Debug)]
 pub struct DragListCtx(
-    RwSignal<Option<FormState<String>>>,
+    RwSignal<Option<Uuid>>,
     RwSignal<Option<(Uuid, String)>>,
-    Callback<(FormState<String>, String, Uuid)>,
+    Callback<(KeyedEntry, String, Uuid)>,
 );
 
 impl DragListCtx {
-    pub fn provide(cb: Callback<(FormState<String>, String, Uuid)>) {
+    pub fn provide(cb: Callback<(KeyedEntry, String, Uuid)>) {
         let origin = create_rw_signal(None);
         let target = create_rw_signal(None);
         provide_context(DragListCtx(origin, target, cb));
@@ -321,16 +397,19 @@ impl DragListCtx {
 
 const TOLLERANCE: f64 = 15_f64;
 
+/// place to drop a dragged entry after another one, shown while dragging
+///
+/// a nil `after` is the place before the first entry
 #[component]
-fn ListDropTarget(
-    #[prop(into)] item_after: FormState<String>,
+pub fn ListDropTarget(
+    #[prop(into)] after: Uuid,
     #[prop(into)] drop_target_name: Signal<String>,
 ) -> impl IntoView {
     let drag_ctx = use_context::<DragListCtx>();
 
     if let Some(ctx) = drag_ctx {
         let el = create_node_ref::<html::Li>();
-        let id_after = item_after.id.clone();
+        let id_after = after;
 
         let UseMouseInElementReturn {
             is_outside,
@@ -379,7 +458,7 @@ fn ListDropTarget(
 
         view! {
             <Show
-                when=move || ctx.0.get().filter(|f| f.id != id_after).is_some()
+                when=move || ctx.0.get().filter(|id| *id != id_after).is_some()
             >
                 <li
                     id={format!("drop-target-{id_after}")}
@@ -1,23 +1,28 @@
 use form_signal::FormState;
 use leptos::*;
+use uuid::Uuid;
 
 use crate::app::components::ReadOnlyView;
 
 use super::CheckedOption;
 
+/// selects entries of a list by their ids, option values are the ids as string
 #[component]
 pub fn ListSelectView(
     #[prop(into)] options: Signal<Vec<CheckedOption>>,
-    #[prop(into)] value: Signal<FormState<Vec<String>>>,
+    #[prop(into)] value: Signal<FormState<Vec<Uuid>>>,
     #[prop(into, optional)] max: MaybeSignal<Option<usize>>,
 ) -> impl IntoView {
     let on_change = move |(ev, attr_value): (ev::Event, String)| {
+        let Ok(id) = Uuid::parse_str(attr_value.as_str()) else {
+            return;
+        };
         let checked = event_target_checked(&ev);
         value.get().update(|val| {
             if checked {
-                val.push(attr_value)
+                val.push(id)
             } else {
-                val.retain(|v| v != &attr_value)
+                val.retain(|v| v != &id)
             }
         });
     };
@@ -30,12 +35,13 @@ pub fn ListSelectView(
             .get()
             .get()
             .into_iter()
+            .map(|v| v.to_string())
             .filter(|v| options.iter().find(|o| &o.value == v).is_none())
             .map(|v| CheckedOption {
-                value: v.clone(),
+                value: v,
                 label: view! {
                     <ReadOnlyView>
-                        <span class="line-through">{v.clone()}</span>
+                        <span class="line-through">{t!("worksheets.compromise.removed_choice")}</span>
                     </ReadOnlyView>
                 },
             })
@@ -78,23 +84,26 @@ pub fn ListSelectView(
 
 #[component]
 pub fn ListSelectItem(
-    #[prop(into)] form: Signal<FormState<Vec<String>>>,
+    #[prop(into)] form: Signal<FormState<Vec<Uuid>>>,
     #[prop(into)] value: MaybeSignal<String>,
     #[prop(into)] max: Signal<Option<usize>>,
     #[prop(into)] on_change: Callback<(ev::Event, String)>,
     children: ChildrenFn,
 ) -> impl IntoView {
     let value = Signal::derive(move || value.get());
-    let checked = Signal::derive(move || form.get().get().contains(&value.get()));
+    let id = Signal::derive(move || Uuid::parse_str(value.get().as_str()).ok());
+    let checked = Signal::derive(move || {
+        id.get()
+            .map(|id| form.get().get().contains(&id))
+            .unwrap_or(false)
+    });
     let name = Signal::derive(move || form.get().id.to_string());
     let on_change = move |ev| on_change.call((ev, value.get()));
 
     let disabled = Signal::derive(move || {
-        let val = value.get();
         let data = form.get().get();
         let len = data.len();
-        let selected = data.contains(&val);
-        !selected && max.get().map(|max| len >= max).unwrap_or(false)
+        !checked.get() && max.get().map(|max| len >= max).unwrap_or(false)
     });
 
     view! {
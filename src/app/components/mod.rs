@@ -3,6 +3,7 @@ mod button;
 mod checkbox_input;
 mod contact;
 mod dark_mode_toggle;
+mod date_input;
 mod description;
 mod error;
 mod footer;
@@ -25,6 +26,7 @@ pub use button::*;
 pub use checkbox_input::*;
 pub use contact::*;
 pub use dark_mode_toggle::*;
+pub use date_input::*;
 pub use description::*;
 pub use error::*;
 pub use footer::*;
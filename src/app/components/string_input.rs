@@ -1,3 +1,5 @@
+use std::fmt::Debug;
+
 use form_signal::FormState;
 use leptos::{
     html::{div, input, textarea},
@@ -9,8 +11,24 @@ use crate::app::components::APP_MAIN;
 
 const AUTOCOMPLETE_OPTION: &str = "_autocomplete-option";
 
+/// value edited as text, e.g. the name of a structured list entry
+pub trait TextValue: Debug + Default + PartialEq + Clone + 'static {
+    fn text(&self) -> String;
+    fn set_text(&mut self, text: String);
+}
+
+impl TextValue for String {
+    fn text(&self) -> String {
+        self.clone()
+    }
+
+    fn set_text(&mut self, text: String) {
+        *self = text;
+    }
+}
+
 #[component]
-pub fn StringInputView(
+pub fn StringInputView<T: TextValue>(
     #[prop(into)] input_type: String,
     #[prop(into, optional)] placeholder: MaybeSignal<String>,
     #[prop(into, optional)] disabled: MaybeSignal<bool>,
@@ -18,7 +36,7 @@ pub fn StringInputView(
     #[prop(into, optional)] auto_focus: MaybeSignal<bool>,
     #[prop(into, optional)] on_blur: Option<Callback<ev::FocusEvent>>,
     #[prop(into, optional)] on_focus: Option<Callback<ev::FocusEvent>>,
-    #[prop(into)] value: Signal<FormState<String>>,
+    #[prop(into)] value: Signal<FormState<T>>,
     #[prop(into, optional)] autocomplete: MaybeSignal<Vec<String>>,
     #[prop(attrs, optional)] attrs: Vec<(&'static str, Attribute)>,
 ) -> impl IntoView {
@@ -31,7 +49,7 @@ pub fn StringInputView(
     let on_input = move |event| {
         let next = value.get();
         let input_value = event_target_value(&event);
-        next.update(move |v| *v = input_value);
+        next.update(move |v| v.set_text(input_value));
 
         let input_value = event_target_value(&event);
         let autocomplete_options = autocomplete
@@ -61,6 +79,7 @@ pub fn StringInputView(
             .try_get()
             .map(|v| v.try_get())
             .flatten()
+            .map(|v| v.text())
             .unwrap_or_default()
     });
 
@@ -119,7 +138,7 @@ pub fn StringInputView(
 
     let on_select_autocomplete = Callback::new(move |input_value: String| {
         let next = value.get();
-        next.update(move |v| *v = input_value);
+        next.update(move |v| v.set_text(input_value));
         set_may_autocomplete.set(false);
     });
 
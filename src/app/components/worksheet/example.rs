@@ -7,6 +7,7 @@ use crate::app::state::{use_store, ExampleParams, ProjectData, WorkSheets};
 struct ExampleCtx {
     wk: Signal<WorkSheets>,
     example: Signal<ProjectData>,
+    shared: bool,
 }
 
 pub fn use_example_ctx() -> (Signal<WorkSheets>, Signal<ProjectData>) {
@@ -18,6 +19,13 @@ pub fn use_example_ctx() -> (Signal<WorkSheets>, Signal<ProjectData>) {
     )
 }
 
+/// whether the example context holds a shared snapshot rather than a project
+pub fn use_example_shared() -> bool {
+    use_context::<ExampleCtx>()
+        .map(|ctx| ctx.shared)
+        .unwrap_or_default()
+}
+
 #[component]
 pub fn ExampleView(children: ChildrenFn) -> impl IntoView {
     let example_id = use_params::<ExampleParams>();
@@ -41,7 +49,27 @@ pub fn ExampleView(children: ChildrenFn) -> impl IntoView {
     });
     let wk = Signal::derive(move || example.get().wk.unwrap_or_default());
 
-    provide_context(ExampleCtx { example, wk });
+    provide_context(ExampleCtx {
+        example,
+        wk,
+        shared: false,
+    });
+
+    children.into_view()
+}
+
+/// provides a read-only snapshot to the example views
+#[component]
+pub fn SharedExampleView(
+    #[prop(into)] wk: Signal<WorkSheets>,
+    #[prop(into)] example: Signal<ProjectData>,
+    children: ChildrenFn,
+) -> impl IntoView {
+    provide_context(ExampleCtx {
+        example,
+        wk,
+        shared: true,
+    });
 
     children.into_view()
 }
@@ -20,8 +20,8 @@ pub fn ContactView() -> impl IntoView {
     });
 
     let disabled = Signal::derive(move || {
-        let value = contact_value.get().get();
-        !value.is_complete() && loaded.get()
+        let value = contact_value.get();
+        (!value.get().is_complete() || !value.check()) && loaded.get()
     });
 
     let inquire_personal_action = create_server_action::<InquireContact>();
@@ -5,6 +5,7 @@ mod not_found;
 mod process;
 mod projects;
 mod resume;
+mod shared;
 
 pub use contact::*;
 pub use landing::*;
@@ -13,3 +14,4 @@ pub use not_found::*;
 pub use process::*;
 pub use projects::*;
 pub use resume::*;
+pub use shared::*;
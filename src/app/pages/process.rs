@@ -1,13 +1,12 @@
 use leptos::*;
 use leptos_meta::*;
 use leptos_router::*;
-use strum::VariantArray;
 
 use crate::app::{
     components::{ErrorView, Tab, WorksheetDummy, WorksheetView},
     process::*,
     projects::get_projects,
-    state::{use_store, ProcessStep, ProjectData, SeqStep, StorageMode},
+    state::{use_store, ProcessStep, ProcessTemplate, ProjectData, SeqStep, StorageMode},
     use_lang, Language,
 };
 
@@ -29,24 +28,18 @@ pub fn ProcessView() -> impl IntoView {
 
     let process_view_with_data = Signal::derive(move || {
         let lang = store.get().lang;
+        let template = store.get().template();
         if let Some(data) = examples.get() {
             let examples = data.map_err(|e| ServerFnErrorErr::from(e))?;
             store.update(|s| {
                 s.examples = examples;
                 s.sequence = vec![];
-                make_sequence(&mut s.sequence, &s.examples, lang);
+                make_sequence(&mut s.sequence, template, &s.examples, lang);
             });
         } else {
             store.update(|s| {
-                s.sequence = ProcessStep::VARIANTS
-                    .iter()
-                    .enumerate()
-                    .map(|(i, step)| SeqStep {
-                        href: format!("/{}/process/{}", lang, i),
-                        process_step: *step,
-                        example: None,
-                    })
-                    .collect();
+                s.sequence = vec![];
+                make_sequence(&mut s.sequence, template, &vec![], lang);
             });
         }
         let storage_type = storage_type.get();
@@ -57,6 +50,7 @@ pub fn ProcessView() -> impl IntoView {
                 fs_element=fullscreen_root
             >
                 <Outlet/>
+                <CollabSyncView/>
             </WorksheetView>
         })
     });
@@ -97,10 +91,7 @@ pub fn ProcessView() -> impl IntoView {
 pub fn tabs_signal(step: ProcessStep) -> Signal<Vec<Tab>> {
     let state = use_store();
 
-    let step_num: usize = ProcessStep::VARIANTS
-        .iter()
-        .position(|s| *s == step)
-        .unwrap();
+    let step_num = step as usize;
 
     Signal::derive(move || {
         let s = state.get();
@@ -122,69 +113,71 @@ pub fn tabs_signal(step: ProcessStep) -> Signal<Vec<Tab>> {
     })
 }
 
-fn make_sequence(seq: &mut Vec<SeqStep>, examples: &Vec<ProjectData>, lang: Language) {
-    // about
-    seq.push(SeqStep {
-        href: format!("/{}/process/{}", lang, 0),
-        process_step: ProcessStep::About,
-        example: None,
-    });
+/// link to the step following a worksheet in the process template
+pub fn next_step_link(step: ProcessStep) -> Signal<String> {
+    next_path_link((step as usize).to_string())
+}
 
-    // all worksheets first example
-    examples.first().iter().for_each(|ex| {
-        seq.extend(
-            ProcessStep::VARIANTS
-                .iter()
-                .enumerate()
-                .filter_map(|(i, step)| {
-                    if i > 0 && i < ProcessStep::VARIANTS.len() - 2 {
-                        Some(SeqStep {
-                            href: format!("/{}/process/{}/{}", lang, i, ex.id),
-                            process_step: *step,
-                            example: Some(ex.id.clone()),
-                        })
-                    } else {
-                        None
-                    }
-                }),
-        );
-    });
+/// link to the step following the one of a route segment
+pub fn next_path_link(path: String) -> Signal<String> {
+    let state = use_store();
 
-    // each workshet examples
-    seq.extend(
-        ProcessStep::VARIANTS
-            .iter()
-            .enumerate()
-            .fold(vec![], |mut acc, (i, step)| {
-                if i > 0 && i < ProcessStep::VARIANTS.len() - 2 {
-                    // example
-                    acc.extend(examples.iter().skip(1).map(|ex| SeqStep {
-                        href: format!("/{}/process/{}/{}", lang, i, ex.id),
-                        process_step: *step,
-                        example: Some(ex.id.clone()),
-                    }));
-                    // worksheet
-                    acc.push(SeqStep {
-                        href: format!("/{}/process/{}", lang, i),
-                        process_step: *step,
-                        example: None,
-                    });
-                }
-                acc
-            }),
-    );
+    Signal::derive(move || {
+        let s = state.get();
+        let path = s
+            .template()
+            .next_path(path.as_str())
+            .unwrap_or_else(|| (ProcessStep::About as usize).to_string());
+        format!("/{}/process/{path}", s.lang)
+    })
+}
 
-    // iterate
-    seq.push(SeqStep {
-        href: format!("/{}/process/{}", lang, 5),
-        process_step: ProcessStep::Iterate,
-        example: None,
-    });
+fn make_sequence(
+    seq: &mut Vec<SeqStep>,
+    template: &ProcessTemplate,
+    examples: &Vec<ProjectData>,
+    lang: Language,
+) {
+    let mut first_example_shown = false;
+
+    for step in template.steps.iter() {
+        let path = step.path();
+
+        if step.examples && !examples.is_empty() {
+            // all worksheets first example
+            if !first_example_shown {
+                examples.first().iter().for_each(|ex| {
+                    seq.extend(
+                        template
+                            .steps
+                            .iter()
+                            .filter(|s| s.examples)
+                            .map(|s| SeqStep {
+                                href: format!("/{}/process/{}/{}", lang, s.path(), ex.id),
+                                path: s.path(),
+                                process_step: s.kind,
+                                example: Some(ex.id.clone()),
+                            }),
+                    );
+                });
+                first_example_shown = true;
+            }
+
+            // each workshet examples
+            seq.extend(examples.iter().skip(1).map(|ex| SeqStep {
+                href: format!("/{}/process/{}/{}", lang, path, ex.id),
+                path: path.clone(),
+                process_step: step.kind,
+                example: Some(ex.id.clone()),
+            }));
+        }
 
-    // inquire
-    seq.push(SeqStep {
-        href: format!("/{}/process/{}", lang, 6),
-        process_step: ProcessStep::Inquire,
-        example: None,
-    });
+        // worksheet
+        seq.push(SeqStep {
+            href: format!("/{}/process/{}", lang, path),
+            path,
+            process_step: step.kind,
+            example: None,
+        });
+    }
 }
@@ -0,0 +1,205 @@
+use leptos::{error::Error, *};
+use leptos_router::*;
+use uuid::Uuid;
+
+use crate::app::{
+    components::{
+        ButtonView, ErrorView, IconView, ModalView, SharedExampleView, Status, StatusView,
+        WorksheetDummy, WorksheetView,
+    },
+    process::{
+        get_shared_worksheets, open_worksheets, revoke_shared_worksheets, ExampleCompromiseView,
+        ExampleImplementView, ExampleProblemView, ExampleSolutionView,
+    },
+    state::{use_store, Completenes, ProjectData, StorageMode, WorkSheets, WorkSheetsFormState},
+    use_lang,
+};
+
+#[derive(Params, PartialEq, Clone)]
+pub struct SharedParams {
+    pub slug: Option<String>,
+}
+
+#[component]
+pub fn SharedWorksheetsView() -> impl IntoView {
+    let store = use_store();
+    let lang = use_lang();
+    let params = use_params::<SharedParams>();
+    let query = use_query_map();
+    let location = use_location();
+
+    let storage_type = create_read_slice(store, |s| {
+        s.storage_preference.get().unwrap_or(StorageMode::None)
+    });
+
+    let slug = Signal::derive(move || params.get().ok().and_then(|p| p.slug));
+
+    // links with a slug are stored on the server
+    let server_data = create_resource(
+        move || slug.get(),
+        |slug| async move {
+            match slug {
+                Some(slug) => get_shared_worksheets(slug).await.map(Some),
+                None => Ok(None),
+            }
+        },
+    );
+
+    // private links carry the worksheets in the fragment, which only the browser sees
+    let private_data = create_local_resource(
+        move || location.hash.get(),
+        |hash| async move { open_worksheets(hash.as_str()) },
+    );
+
+    let shared_wk = Signal::derive(move || {
+        if slug.get().is_some() {
+            server_data.get().map(|r| {
+                r.map_err(|e| Error::from(ServerFnErrorErr::from(e)))
+                    .and_then(|wk| {
+                        wk.ok_or(Error::from(ServerFnErrorErr::<String>::Request(
+                            "Not found".to_string(),
+                        )))
+                    })
+            })
+        } else {
+            private_data.get().map(|r| r.map_err(Error::from))
+        }
+    });
+
+    // the owner opens the link with the revoke token to withdraw the snapshot
+    let revoke_token = Signal::derive(move || {
+        query
+            .get()
+            .get("revoke")
+            .and_then(|t| Uuid::parse_str(t.as_str()).ok())
+    });
+    let revoke_action = create_action(move |revoke_token: &Uuid| {
+        let revoke_token = *revoke_token;
+        let slug = slug.get_untracked().unwrap_or_default();
+        async move {
+            revoke_shared_worksheets(slug, revoke_token)
+                .await
+                .map_err(ServerFnErrorErr::from)
+        }
+    });
+    let revoked = Signal::derive(move || matches!(revoke_action.value().get(), Some(Ok(_))));
+    let revoke_failed = Signal::derive(move || matches!(revoke_action.value().get(), Some(Err(_))));
+
+    create_effect(move |_| {
+        if revoked.get() {
+            server_data.refetch();
+        }
+    });
+
+    let on_revoke = move |_| {
+        if let Some(revoke_token) = revoke_token.get_untracked() {
+            revoke_action.dispatch(revoke_token);
+        }
+    };
+
+    let (show_import, set_show_import) = create_signal(false);
+    let has_data = Signal::derive(move || !store.get().wk.get().is_empty());
+
+    let navigate = use_navigate();
+    let on_import = Callback::new(move |confirmed: bool| {
+        set_show_import.set(false);
+        if !confirmed {
+            return;
+        }
+        if let Some(Ok(wk)) = shared_wk.get_untracked() {
+            store.update(|s| s.wk = WorkSheetsFormState::new(wk));
+            let link = format!("/{}/process/1", lang.get_untracked());
+            navigate(link.as_str(), Default::default());
+        }
+    });
+
+    let on_import_click = move |_| {
+        if has_data.get() {
+            set_show_import.set(true);
+        } else {
+            on_import.call(true);
+        }
+    };
+
+    let shared_view = move || {
+        shared_wk.get().map(|data| {
+            let wk: WorkSheets = data?;
+            let example = Signal::derive(move || ProjectData {
+                id: slug.get().unwrap_or_default(),
+                title: wk.iterate.title.clone(),
+                description: t!("worksheets.share.shared_description").to_string(),
+                wk: Some(wk.clone()),
+                ..Default::default()
+            });
+            let wk = Signal::derive(move || example.get().wk.unwrap_or_default());
+
+            leptos::error::Result::<View>::Ok(
+                view! {
+                    <WorksheetView storage_type=storage_type.get()>
+                        <div class="flex w-full mb-6 gap-4 justify-end">
+                            <Show when=move || slug.get().is_some() && revoke_token.get().is_some()>
+                                <ButtonView
+                                    cta=-1
+                                    on:click=on_revoke
+                                    disabled=revoke_action.pending()
+                                >
+                                    <IconView icon="Delete"/>
+                                    {t!("worksheets.share.revoke")}
+                                </ButtonView>
+                            </Show>
+                            <ButtonView
+                                cta=2
+                                on:click=on_import_click
+                            >
+                                <IconView icon="Restore"/>
+                                {t!("worksheets.share.import")}
+                            </ButtonView>
+                        </div>
+                        <SharedExampleView wk example>
+                            <ExampleProblemView/>
+                            <hr class="border-t border-slate-400 my-8"/>
+                            <ExampleSolutionView/>
+                            <hr class="border-t border-slate-400 my-8"/>
+                            <ExampleCompromiseView/>
+                            <hr class="border-t border-slate-400 my-8"/>
+                            <ExampleImplementView/>
+                        </SharedExampleView>
+                    </WorksheetView>
+                }
+                .into_view(),
+            )
+        })
+    };
+
+    view! {
+        <div class="grow mx-auto w-full max-w-screen-2xl px-6 md:px-8 lg:px-16">
+            <section class="grow p-8 my-6 lg:my-8 lg:mb-20 bg-stone-200 dark:bg-stone-800 rounded-xl shadow">
+                <Show when=move || revoked.get()>
+                    <StatusView
+                        status=Status::Success
+                        message={t!("worksheets.share.revoked").to_string()}
+                        attr:class="mx-auto mb-6"
+                    />
+                </Show>
+                <Show when=move || revoke_failed.get()>
+                    <p class="text-sm opacity-80 text-center mb-6">
+                        {t!("worksheets.share.revoke_failed")}
+                    </p>
+                </Show>
+                <Transition fallback={WorksheetDummy}>
+                    <ErrorBoundary fallback=|err| view! { <ErrorView errors=err/>}>
+                        {shared_view}
+                    </ErrorBoundary>
+                </Transition>
+            </section>
+        </div>
+        <ModalView
+            when=show_import
+            cancel_btn=true
+            on_resolve=on_import
+        >
+            <h3 class="text-xl mb-2">{t!("worksheets.share.import")}</h3>
+            <p class="max-w-prose mb-4 whitespace-pre-line">{t!("worksheets.share.import_warning")}</p>
+        </ModalView>
+    }
+}
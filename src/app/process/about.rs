@@ -1,9 +1,13 @@
 use crate::app::{
     components::{
-        ButtonSize, ButtonView, HistoryEntry, IconView, RvArtboardView, UndoRemove, WorksheetHeader,
+        ButtonSize, ButtonView, CheckedOption, HistoryEntry, IconView, RadioInputView,
+        RvArtboardView, UndoRemove, WorksheetHeader,
+    },
+    next_step_link,
+    process::CollabView,
+    state::{
+        use_store, Completenes, ProcessStep, ProcessTemplate, WorkSheets, WorkSheetsFormState,
     },
-    state::{use_store, Completenes, ProcessStep, WorkSheets, WorkSheetsFormState},
-    use_lang,
 };
 
 use leptos::*;
@@ -13,7 +17,6 @@ use web_time::Instant;
 /// step 1
 #[component]
 pub fn AboutView() -> impl IntoView {
-    let lang = use_lang();
     let state = use_store();
 
     let show_privacy_choice =
@@ -33,6 +36,24 @@ pub fn AboutView() -> impl IntoView {
         state.update(|s| s.wk = WorkSheetsFormState::new(wk));
     };
 
+    let template = Signal::derive(move || state.get().wk.template);
+    let template_options = Signal::derive(|| {
+        ProcessTemplate::all()
+            .iter()
+            .map(|t| CheckedOption {
+                value: t.id.clone(),
+                label: view! {
+                    <p class="max-w-prose whitespace-pre-line">
+                        <strong>{t!(t.title.as_str()).to_string()}</strong>
+                        <br/>
+                        {t!(t.description.as_str()).to_string()}
+                    </p>
+                }
+                .into_view(),
+            })
+            .collect::<Vec<_>>()
+    });
+
     let steps = vec![
         ProcessStep::Problem,
         ProcessStep::Solution,
@@ -83,7 +104,7 @@ pub fn AboutView() -> impl IntoView {
                 </ol>
                 <div class="max-w-prose col-start-1 lg:row-start-3 flex mt-auto items-center">
                     <ButtonView
-                        link={Signal::derive(move || format!("/{}/process/1", lang.get()))}
+                        link={next_step_link(ProcessStep::About)}
                         size=ButtonSize::Lg
                         cta=2
                         attr:class="shrink-0 grow"
@@ -96,6 +117,10 @@ pub fn AboutView() -> impl IntoView {
                         <IconView icon="Next"/>
                     </ButtonView>
                 </div>
+                <div class="max-w-prose col-start-1" id="about-template">
+                    <p class="mb-2">{t!("templates.instruction")}</p>
+                    <RadioInputView options=template_options value=template/>
+                </div>
                 <div class="max-w-prose col-start-1 flex justify-between items-stretch gap-4">
                     <Show when=move || has_data.get()>
                         <ButtonView
@@ -119,6 +144,9 @@ pub fn AboutView() -> impl IntoView {
                         </ButtonView>
                     </Show>
                 </div>
+                <div class="max-w-prose col-start-1 flex flex-wrap items-stretch gap-4">
+                    <CollabView/>
+                </div>
             </div>
         </div>
         <UndoRemove
@@ -0,0 +1,233 @@
+use form_signal::FormState;
+use leptos::*;
+use uuid::Uuid;
+
+use crate::app::{
+    components::{
+        use_wk_state, ButtonSize, ButtonView, DragChipView, DragListCtx, IconView, ListDropTarget,
+    },
+    process::INPUT_CLASS,
+    state::{KeyedEntry, ProblemCluster, ProblemWK},
+};
+
+const UNCLUSTERED: &str = "unclustered";
+
+/// problems dragged into named clusters
+#[component]
+pub fn AffinityMapView() -> impl IntoView {
+    let wk_state = use_wk_state();
+
+    let problem = Signal::derive(move || {
+        let problem: ProblemWK = (&wk_state.get().problem.get()).into();
+        problem
+    });
+    let clusters = Signal::derive(move || problem.get().clusters);
+    let unclustered = Signal::derive(move || problem.get().unclustered());
+
+    // the drop target name is the id of a cluster
+    DragListCtx::provide(Callback::new(
+        move |((id, _), target, insert_after): (KeyedEntry, String, Uuid)| {
+            let cluster = Uuid::parse_str(target.as_str()).ok();
+            wk_state
+                .get_untracked()
+                .problem
+                .get_untracked()
+                .move_to_cluster(id, cluster, insert_after);
+        },
+    ));
+
+    let update = move |id: Uuid, f: &dyn Fn(&mut ProblemCluster)| {
+        wk_state
+            .get_untracked()
+            .problem
+            .get_untracked()
+            .clusters
+            .update(|c| c.iter_mut().filter(|c| c.id == id).for_each(f));
+    };
+
+    let on_add = move |e: ev::MouseEvent| {
+        e.prevent_default();
+        wk_state
+            .get_untracked()
+            .problem
+            .get_untracked()
+            .clusters
+            .update(|c| c.push(ProblemCluster::new()));
+    };
+
+    let on_remove = move |id: Uuid| {
+        wk_state
+            .get_untracked()
+            .problem
+            .get_untracked()
+            .clusters
+            .update(|c| c.retain(|c| c.id != id));
+    };
+
+    let chips = move |entries: Vec<KeyedEntry>, target: String| {
+        let target = Signal::derive(move || target.clone());
+        view! {
+            <ListDropTarget after=Uuid::nil() drop_target_name=target/>
+            {entries
+                .into_iter()
+                .map(|(id, text)| {
+                    let item = FormState::with_id(id, text);
+                    view! {
+                        <DragChipView item/>
+                        <ListDropTarget after=id drop_target_name=target/>
+                    }
+                })
+                .collect_view()}
+        }
+    };
+
+    view! {
+        <div id="problem-clusters" class="text-left mb-4">
+            <h4 class="text-xl mb-4 text-center">
+                {t!("worksheets.problem.affinity.title")}
+            </h4>
+            <div class="max-w-prose mb-4 whitespace-pre-line">
+                <p>{t!("worksheets.problem.affinity.instruction")}</p>
+            </div>
+            <div class="grid lg:grid-cols-[1fr_2fr] gap-6">
+                <div>
+                    <p class="mb-2">{t!("worksheets.problem.affinity.unclustered")}</p>
+                    <ul class="min-h-16 p-2 rounded border border-dashed border-slate-400">
+                        {move || chips(unclustered.get(), UNCLUSTERED.to_string())}
+                    </ul>
+                </div>
+                <div class="grid md:grid-cols-2 gap-4 content-start">
+                    <For
+                        each=move || clusters.get()
+                        key=|c| c.id
+                        let:cluster
+                    >
+                        {
+                            let id = cluster.id;
+                            let name = Signal::derive(move || {
+                                clusters
+                                    .get()
+                                    .into_iter()
+                                    .find(|c| c.id == id)
+                                    .map(|c| c.name)
+                                    .unwrap_or_default()
+                            });
+                            let entries = move || {
+                                let problem = problem.get();
+                                problem
+                                    .clusters
+                                    .iter()
+                                    .find(|c| c.id == id)
+                                    .map(|c| {
+                                        c.problems
+                                            .iter()
+                                            .filter_map(|p| problem.problems.iter().find(|(e, _)| e == p))
+                                            .cloned()
+                                            .collect::<Vec<_>>()
+                                    })
+                                    .unwrap_or_default()
+                            };
+                            view! {
+                                <div class="p-2 rounded border border-slate-400">
+                                    <div class="grid grid-cols-[1fr_auto] gap-2 items-center mb-2">
+                                        <input
+                                            type="text"
+                                            class=INPUT_CLASS
+                                            aria-label=t!("worksheets.problem.affinity.label_cluster").to_string()
+                                            placeholder=t!("worksheets.problem.affinity.placeholder_cluster").to_string()
+                                            prop:value=name
+                                            on:input=move |ev| {
+                                                let value = event_target_value(&ev);
+                                                update(id, &|c| c.name = value.clone());
+                                            }
+                                        />
+                                        <ButtonView
+                                            cta=-1
+                                            attr:title=t!("worksheets.problem.affinity.remove_cluster").to_string()
+                                            attr:aria-label=t!("worksheets.problem.affinity.remove_cluster").to_string()
+                                            on:click=move |e| {
+                                                e.prevent_default();
+                                                on_remove(id);
+                                            }
+                                        >
+                                            <IconView icon="Delete"/>
+                                        </ButtonView>
+                                    </div>
+                                    <ul class="min-h-16">
+                                        {move || chips(entries(), id.to_string())}
+                                    </ul>
+                                </div>
+                            }
+                        }
+                    </For>
+                    <div class="flex items-center justify-center">
+                        <ButtonView on:click=on_add>
+                            {t!("worksheets.problem.affinity.add_cluster")}
+                        </ButtonView>
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+/// themes of the affinity map offered while writing the problem statement
+#[component]
+pub fn ThemesScaffoldView() -> impl IntoView {
+    let wk_state = use_wk_state();
+
+    let problem = Signal::derive(move || {
+        let problem: ProblemWK = (&wk_state.get().problem.get()).into();
+        problem
+    });
+    let themes = Signal::derive(move || problem.get().themes());
+
+    let on_draft = move |e: ev::MouseEvent| {
+        e.prevent_default();
+        let names = themes
+            .get_untracked()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>()
+            .join(", ");
+        wk_state
+            .get_untracked()
+            .problem
+            .get_untracked()
+            .problem_statement
+            .set(t!("worksheets.problem.affinity.draft", themes = names).to_string());
+    };
+    let disable_draft = Signal::derive(move || !problem.get().problem_statement.is_empty());
+
+    view! {
+        <Show when=move || !themes.get().is_empty()>
+            <div class="max-w-prose mb-4">
+                <p class="mb-2">{t!("worksheets.problem.affinity.label_themes")}</p>
+                <ul class="list-disc pl-6 mb-2">
+                    {move || themes
+                        .get()
+                        .into_iter()
+                        .map(|(name, problems)| view! {
+                            <li>
+                                <strong>{name}</strong>
+                                <ul class="list-[circle] pl-6 text-sm">
+                                    {problems
+                                        .into_iter()
+                                        .map(|p| view! { <li class="whitespace-pre-line">{p}</li> })
+                                        .collect_view()}
+                                </ul>
+                            </li>
+                        })
+                        .collect_view()}
+                </ul>
+                <ButtonView
+                    size=ButtonSize::Sm
+                    disabled=disable_draft
+                    on:click=on_draft
+                >
+                    {t!("worksheets.problem.affinity.draft_statement")}
+                </ButtonView>
+            </div>
+        </Show>
+    }
+}
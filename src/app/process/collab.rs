@@ -0,0 +1,391 @@
+#![cfg_attr(not(feature = "ssr"), allow(unused))]
+
+use std::time::Duration;
+
+use form_signal::FormState;
+use leptos::*;
+use uuid::Uuid;
+
+use crate::app::{
+    components::{ButtonView, ErrorView, IconView, ModalView, StringInputView},
+    state::{use_store, CollabEntry, CollabSession},
+    util::copy_to_clipboard,
+};
+
+#[cfg(feature = "ssr")]
+use crate::server::safe_error;
+#[cfg(feature = "ssr")]
+use spin_sdk::sqlite::{Connection, Value};
+
+#[cfg_attr(feature = "ssr", allow(unused))]
+const COLLAB_POLL_INTERVAL: Duration = Duration::from_secs(3);
+
+const COLLAB_SESSION_TTL_DAYS: i64 = 30;
+
+/// most changes accepted in one sync
+const COLLAB_CHANGES_MAX: usize = 500;
+
+/// longest value of an entry, in bytes
+const COLLAB_VALUE_MAX_LEN: usize = 4 * 1024;
+
+/// most entries kept by a session, removed entries included
+const COLLAB_SESSION_MAX_ENTRIES: i64 = 5000;
+
+/// the code is the only secret of a session, it carries 128 random bits
+#[cfg(feature = "ssr")]
+fn session_code() -> String {
+    use aes_gcm::aead::{rand_core::RngCore, OsRng};
+
+    let mut bytes = [0_u8; 16];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{b:02X}")).collect()
+}
+
+#[server(CreateCollabSession, "/api")]
+pub async fn create_collab_session() -> Result<String, ServerFnError<String>> {
+    let code = session_code();
+
+    let conn = Connection::open("default").map_err(safe_error)?;
+
+    _ = conn
+        .execute(
+            r#"
+        DELETE FROM collab_entry WHERE session IN (
+            SELECT code FROM collab_session WHERE updated_date < unixepoch() - ? * 86400
+        );
+"#,
+            &[Value::Integer(COLLAB_SESSION_TTL_DAYS)],
+        )
+        .map_err(safe_error)?;
+    _ = conn
+        .execute(
+            "DELETE FROM collab_session WHERE updated_date < unixepoch() - ? * 86400;",
+            &[Value::Integer(COLLAB_SESSION_TTL_DAYS)],
+        )
+        .map_err(safe_error)?;
+
+    let sql = r#"
+        INSERT INTO collab_session
+        (code, created_date, updated_date)
+        VALUES (?, unixepoch(), unixepoch())
+"#;
+    _ = conn
+        .execute(sql, &[Value::Text(code.clone())])
+        .map_err(safe_error)?;
+
+    println!("created collab session {code}");
+
+    Ok(code)
+}
+
+/// stores the changes in one revision, fails when the session would exceed its entries,
+/// must run in a transaction
+#[cfg(feature = "ssr")]
+fn store_changes(
+    conn: &Connection,
+    code: &str,
+    changes: Vec<CollabEntry>,
+) -> Result<(), ServerFnError<String>> {
+    let next_revision = conn
+        .execute(
+            r#"
+        UPDATE collab_session
+        SET revision = revision + 1, updated_date = unixepoch()
+        WHERE code = ?
+        RETURNING revision;
+"#,
+            &[Value::Text(code.to_string())],
+        )
+        .map_err(safe_error)?
+        .rows()
+        .next()
+        .and_then(|row| row.get::<i64>("revision"))
+        .ok_or(ServerFnError::Request("Not found".to_string()))?;
+
+    let sql = r#"
+        INSERT INTO collab_entry
+        (session, field, id, position, value, deleted, revision)
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT (session, field, id) DO UPDATE SET
+            position = excluded.position,
+            value = excluded.value,
+            deleted = excluded.deleted,
+            revision = excluded.revision;
+"#;
+    for entry in changes {
+        _ = conn
+            .execute(
+                sql,
+                &[
+                    Value::Text(code.to_string()),
+                    Value::Text(entry.field),
+                    Value::Blob(Vec::from(entry.id.as_bytes())),
+                    Value::Integer(entry.position as i64),
+                    Value::Text(entry.value),
+                    Value::Integer(entry.deleted as i64),
+                    Value::Integer(next_revision),
+                ],
+            )
+            .map_err(safe_error)?;
+    }
+
+    let entries = conn
+        .execute(
+            "SELECT COUNT(*) AS entries FROM collab_entry WHERE session = ?;",
+            &[Value::Text(code.to_string())],
+        )
+        .map_err(safe_error)?
+        .rows()
+        .next()
+        .and_then(|row| row.get::<i64>("entries"))
+        .unwrap_or_default();
+
+    if entries > COLLAB_SESSION_MAX_ENTRIES {
+        return Err(ServerFnError::Request(
+            "Too many entries in the session".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// stores local changes and returns everything changed since `revision`,
+/// `None` once the session ended or expired
+#[server(SyncCollabSession, "/api")]
+pub async fn sync_collab_session(
+    code: String,
+    revision: i64,
+    changes: Vec<CollabEntry>,
+) -> Result<Option<Vec<CollabEntry>>, ServerFnError<String>> {
+    if changes.len() > COLLAB_CHANGES_MAX {
+        return Err(ServerFnError::Request("Too many changes".to_string()));
+    }
+    if changes.iter().any(|e| e.value.len() > COLLAB_VALUE_MAX_LEN) {
+        return Err(ServerFnError::Request("Entry is too long".to_string()));
+    }
+
+    let conn = Connection::open("default").map_err(safe_error)?;
+
+    let exists = conn
+        .execute(
+            "SELECT code FROM collab_session WHERE code = ? AND updated_date >= unixepoch() - ? * 86400;",
+            &[
+                Value::Text(code.clone()),
+                Value::Integer(COLLAB_SESSION_TTL_DAYS),
+            ],
+        )
+        .map_err(safe_error)?
+        .rows()
+        .next()
+        .is_some();
+
+    if !exists {
+        return Ok(None);
+    }
+
+    if !changes.is_empty() {
+        // changes of one participant become visible at once
+        _ = conn.execute("BEGIN IMMEDIATE;", &[]).map_err(safe_error)?;
+
+        match store_changes(&conn, code.as_str(), changes) {
+            Ok(_) => _ = conn.execute("COMMIT;", &[]).map_err(safe_error)?,
+            Err(e) => {
+                _ = conn.execute("ROLLBACK;", &[]);
+                return Err(e);
+            }
+        }
+    }
+
+    let sql = r#"
+        SELECT field, id, position, value, deleted, revision FROM collab_entry
+        WHERE session = ? AND revision > ?
+        ORDER BY revision;
+"#;
+    let data = conn
+        .execute(sql, &[Value::Text(code), Value::Integer(revision)])
+        .map_err(safe_error)?;
+
+    let entries = data
+        .rows()
+        .filter_map(|row| {
+            Some(CollabEntry {
+                field: row.get::<&str>("field")?.to_string(),
+                id: Uuid::from_slice(row.get::<&[u8]>("id")?).ok()?,
+                position: row.get::<i64>("position")? as u32,
+                value: row.get::<&str>("value")?.to_string(),
+                deleted: row.get::<i64>("deleted")? != 0,
+                revision: row.get::<i64>("revision")?,
+            })
+        })
+        .collect();
+
+    Ok(Some(entries))
+}
+
+#[component]
+pub fn CollabSyncView() -> impl IntoView {
+    let store = use_store();
+    let collab = create_read_slice(store, |s| s.collab);
+
+    let sync_action = create_action(|data: &(String, i64, Vec<CollabEntry>)| {
+        let (code, revision, changes) = data.clone();
+        async move {
+            sync_collab_session(code.clone(), revision, changes)
+                .await
+                .map(|entries| (code, entries))
+                .map_err(ServerFnErrorErr::from)
+        }
+    });
+
+    #[cfg_attr(feature = "ssr", allow(unused_variables))]
+    let on_poll = move || {
+        if sync_action.pending().get_untracked() {
+            return;
+        }
+        let collab = collab.get_untracked();
+        let wk = store.get_untracked().wk;
+        if let Some(session) = collab.get_untracked() {
+            let changes = session.changes(&wk);
+            sync_action.dispatch((session.code, session.revision, changes));
+        }
+    };
+
+    #[cfg(feature = "client")]
+    {
+        let handle = set_interval_with_handle(on_poll, COLLAB_POLL_INTERVAL);
+        on_cleanup(move || {
+            if let Ok(handle) = handle {
+                handle.clear();
+            }
+        });
+    }
+
+    create_effect(move |_| {
+        let collab = collab.get_untracked();
+        let wk = store.get_untracked().wk;
+        match sync_action.value().get() {
+            Some(Ok((code, Some(entries)))) => collab.update(|session| {
+                // the session could have been left while syncing
+                if let Some(session) = session.as_mut().filter(|s| s.code == code) {
+                    session.apply(&wk, entries);
+                }
+            }),
+            Some(Ok((code, None))) => collab.update(|session| {
+                if session.as_ref().is_some_and(|s| s.code == code) {
+                    *session = None;
+                }
+            }),
+            Some(Err(e)) => log::error!("collab sync failed: {e}"),
+            None => {}
+        }
+    });
+}
+
+#[component]
+pub fn CollabView() -> impl IntoView {
+    let store = use_store();
+    let collab = create_read_slice(store, |s| s.collab);
+    let (show_join, set_show_join) = create_signal(false);
+    let (copied, set_copied) = create_signal(false);
+    let join_code = create_rw_signal(FormState::new(String::default()));
+
+    let code = Signal::derive(move || collab.get().get().map(|s| s.code));
+
+    let create_session = create_action(|_: &()| async move {
+        create_collab_session()
+            .await
+            .map_err(ServerFnErrorErr::from)
+    });
+
+    create_effect(move |_| {
+        if let Some(Ok(code)) = create_session.value().get() {
+            collab
+                .get_untracked()
+                .set(Some(CollabSession::new(code, false)));
+        }
+    });
+
+    let on_join = Callback::new(move |confirmed: bool| {
+        set_show_join.set(false);
+        let code = join_code.get_untracked().get_untracked();
+        let code = code.split_whitespace().collect::<String>().to_uppercase();
+        if confirmed && !code.is_empty() {
+            collab
+                .get_untracked()
+                .set(Some(CollabSession::new(code, true)));
+        }
+        join_code.get_untracked().set(String::default());
+    });
+
+    let on_copy = move |_| {
+        if let Some(code) = code.get() {
+            copy_to_clipboard(code.as_str());
+            set_copied.set(true);
+            set_timeout(move || set_copied.set(false), Duration::from_secs(3));
+        }
+    };
+
+    let on_leave = move |_| {
+        collab.get().set(None);
+        create_session.value().set(None);
+    };
+
+    view! {
+        <Show
+            when=move || code.get().is_some()
+            fallback=move || view! {
+                <ButtonView
+                    on:click=move |_| create_session.dispatch(())
+                    disabled=create_session.pending()
+                >
+                    <IconView icon="Send"/>
+                    {t!("worksheets.collab.start")}
+                </ButtonView>
+                <ButtonView
+                    on:click=move |_| set_show_join.set(true)
+                >
+                    {t!("worksheets.collab.join")}
+                </ButtonView>
+            }
+        >
+            <p class="self-center">
+                {t!("worksheets.collab.label_code")}
+                " "
+                <code class="font-bold">{code}</code>
+            </p>
+            <ButtonView
+                cta=1
+                on:click=on_copy
+            >
+                {move || if copied.get() {
+                    t!("worksheets.download.copied").to_string()
+                } else {
+                    t!("worksheets.collab.copy").to_string()
+                }}
+            </ButtonView>
+            <ButtonView
+                cta=-1
+                on:click=on_leave
+            >
+                <IconView icon="Close"/>
+                {t!("worksheets.collab.leave")}
+            </ButtonView>
+        </Show>
+        <ErrorBoundary fallback=|err| view! { <ErrorView errors=err/>}>
+            {move || create_session.value().get().map(|r| r.map(|_| ()))}
+        </ErrorBoundary>
+        <ModalView
+            when=show_join
+            cancel_btn=true
+            on_resolve=on_join
+        >
+            <h3 class="text-xl mb-2">{t!("worksheets.collab.join")}</h3>
+            <p class="max-w-prose mb-4 whitespace-pre-line">{t!("worksheets.collab.join_description")}</p>
+            <StringInputView
+                input_type="text"
+                value=join_code
+                placeholder={t!("worksheets.collab.label_code").to_string()}
+            />
+        </ModalView>
+    }
+}
@@ -4,12 +4,15 @@ use leptos_router::*;
 
 use crate::app::{
     components::{
-        use_example_ctx, use_wk_ctx, use_wk_state, ButtonSize, ButtonView, CheckedOption,
-        DescriptionView, ListSelectView, ReadOnlyListView, ReadOnlyView, StringInputView,
-        WorksheetHeader,
+        use_example_ctx, use_example_shared, use_wk_ctx, use_wk_state, ButtonSize, ButtonView,
+        CheckedOption, DescriptionView, ListSelectView, ReadOnlyListView, ReadOnlyView,
+        StringInputView, WorksheetHeader,
     },
-    process::FixedProblemStatement,
-    state::{Completenes, ProblemWK, ProcessStep, SolutionsWK},
+    next_step_link,
+    process::{
+        FixedProblemStatement, SolutionChartView, SuggestTarget, SuggestView, ValidationHintView,
+    },
+    state::{keyed_values, ProblemWK, ProcessStep, SolutionsWK, WorkSheets},
     tabs_signal, use_lang,
 };
 
@@ -18,8 +21,7 @@ use crate::app::{
 pub fn CompromiseView() -> impl IntoView {
     let wk_state = use_wk_state();
     let wk_ctx = use_wk_ctx();
-    let lang = use_lang();
-    let link = Signal::derive(move || format!("/{}/process/4", lang.get()));
+    let link = next_step_link(ProcessStep::Compromise);
 
     let question_statement = Signal::derive(move || {
         wk_state
@@ -33,11 +35,10 @@ pub fn CompromiseView() -> impl IntoView {
     let solutions_list = Signal::derive(move || {
         let wk_data: SolutionsWK = (&wk_state.get().solutions.get()).into();
         wk_data
-            .solutions
+            .ranked_solutions()
             .into_iter()
-            .filter(|s| !s.is_empty())
-            .map(|s| CheckedOption {
-                value: s.clone(),
+            .map(|(id, s)| CheckedOption {
+                value: id.to_string(),
                 label: view! {
                     <ReadOnlyView>
                         {s.clone()}
@@ -50,13 +51,13 @@ pub fn CompromiseView() -> impl IntoView {
     let stakeholders_list = Signal::derive(move || {
         let wk_data: ProblemWK = (&wk_state.get().problem.get()).into();
         wk_data
-            .unique_stakeholders()
+            .unique_stakeholder_entries()
             .into_iter()
-            .map(|s| CheckedOption {
-                value: s.clone(),
+            .map(|(id, s)| CheckedOption {
+                value: id.to_string(),
                 label: view! {
                     <ReadOnlyView>
-                        {s.clone()}
+                        {s.name.clone()}
                     </ReadOnlyView>
                 },
             })
@@ -84,21 +85,18 @@ pub fn CompromiseView() -> impl IntoView {
     let tabs = tabs_signal(ProcessStep::Compromise);
 
     let disable_question = Signal::derive(move || {
-        let data = wk_state.get().compromise.get().get();
-        data.solution_choices
-            .iter()
-            .filter(|e| !e.is_empty())
-            .next()
-            .is_none()
-            || data
-                .stakeholder_choices
-                .iter()
-                .filter(|e| !e.is_empty())
-                .next()
-                .is_none()
+        let data = wk_state.get().get();
+        data.solution_choices().is_empty() || data.stakeholder_choices().is_empty()
     });
 
-    let disable_cta = Signal::derive(move || !wk_state.get().compromise.get().get().is_complete());
+    let report = Signal::derive(move || {
+        wk_state
+            .get()
+            .get()
+            .step_report(ProcessStep::Compromise)
+            .unwrap_or_default()
+    });
+    let disable_cta = Signal::derive(move || !report.get().is_valid());
 
     view! {
         <Title text={move || format!("{} | {} | {}", t!("worksheets.compromise.title"), t!("process.title"), t!("name"))}/>
@@ -121,8 +119,9 @@ pub fn CompromiseView() -> impl IntoView {
                     <p>{t!("worksheets.compromise.instruction_1")}</p>
                 </div>
                 <FixedProblemStatement/>
+                <SolutionChartView/>
                 <div class="grid lg:grid-cols-2 gap-6 mt-8">
-                    <div>
+                    <div id="compromise-solution_choices">
                         <h4 class="text-xl mb-4 w-full text-center">
                             {t!("worksheets.compromise.label_solutions")}
                         </h4>
@@ -132,7 +131,7 @@ pub fn CompromiseView() -> impl IntoView {
                             value={solution_choices}
                         />
                     </div>
-                    <div>
+                    <div id="compromise-stakeholder_choices">
                         <h4 class="text-xl mb-4 w-full text-center">
                             {t!("worksheets.compromise.label_stakeholders")}
                         </h4>
@@ -147,7 +146,7 @@ pub fn CompromiseView() -> impl IntoView {
                 <div class="max-w-prose mb-4 whitespace-pre-line">
                     <p>{t!("worksheets.compromise.instruction_2")}</p>
                 </div>
-                <label>
+                <label id="compromise-question">
                     <p class="mb-2">{t!("worksheets.compromise.label_question")}</p>
                     <StringInputView
                         input_type="textarea"
@@ -155,6 +154,19 @@ pub fn CompromiseView() -> impl IntoView {
                         value=question_statement
                         placeholder={t!("worksheets.compromise.placeholder").to_string()}/>
                 </label>
+                <Show when=move || !disable_question.get()>
+                    <SuggestView
+                        target=SuggestTarget::Research
+                        on_accept=move |value: String| {
+                            question_statement.get_untracked().update(|q| {
+                                if !q.is_empty() {
+                                    q.push('\n');
+                                }
+                                q.push_str(value.as_str());
+                            });
+                        }
+                    />
+                </Show>
             </form>
             <div class="flex w-full mt-8 justify-center">
                 <ButtonView
@@ -166,6 +178,7 @@ pub fn CompromiseView() -> impl IntoView {
                     {t!("worksheets.compromise.cta")}
                 </ButtonView>
             </div>
+            <ValidationHintView report/>
         </div>
     }
 }
@@ -204,12 +217,8 @@ pub fn FixedSolutionsChoice() -> impl IntoView {
     let state = use_wk_state();
 
     let solutions = Signal::derive(move || {
-        state
-            .get()
-            .compromise
-            .try_get()
-            .map(|v| v.solution_choices.get())
-            .unwrap_or_default()
+        let wk: WorkSheets = state.get().get();
+        wk.solution_choices()
     });
 
     view! {
@@ -225,12 +234,8 @@ pub fn FixedStakeholdersChoice() -> impl IntoView {
     let state = use_wk_state();
 
     let stakeholders = Signal::derive(move || {
-        state
-            .get()
-            .compromise
-            .try_get()
-            .map(|v| v.stakeholder_choices.get())
-            .unwrap_or_default()
+        let wk: WorkSheets = state.get().get();
+        wk.stakeholder_choices()
     });
 
     view! {
@@ -245,15 +250,20 @@ pub fn FixedStakeholdersChoice() -> impl IntoView {
 pub fn ExampleCompromiseView() -> impl IntoView {
     let lang = use_lang();
     let (wk, example) = use_example_ctx();
+    let shared = use_example_shared();
     let wk_ctx = use_wk_ctx();
 
-    let tabs = tabs_signal(ProcessStep::Compromise);
+    let tabs = if shared {
+        Signal::derive(Vec::new)
+    } else {
+        tabs_signal(ProcessStep::Compromise)
+    };
 
     let question_statement = Signal::derive(move || wk.get().compromise.question);
     let problem_statement = Signal::derive(move || wk.get().problem.problem_statement);
 
-    let solutions_data = Signal::derive(move || wk.get().solutions.solutions);
-    let stakeholders_data = Signal::derive(move || wk.get().problem.stakeholders);
+    let solutions_data = Signal::derive(move || keyed_values(&wk.get().solutions.solutions));
+    let stakeholders_data = Signal::derive(move || keyed_values(&wk.get().problem.stakeholders));
 
     let title = Signal::derive(move || {
         t!(
@@ -286,9 +296,11 @@ pub fn ExampleCompromiseView() -> impl IntoView {
                 <p class="whitespace-pre-line">
                     {move || example.get().description}
                 </p>
-                <A href=case_href attr:class="underline">
-                    {move || t!("worksheets.view_example", title=example.get().title)}
-                </A>
+                <Show when=move || !shared>
+                    <A href=case_href attr:class="underline">
+                        {move || t!("worksheets.view_example", title=example.get().title)}
+                    </A>
+                </Show>
             </DescriptionView>
             <div role="form">
                 <div class="max-w-prose mb-4 whitespace-pre-line italic">
@@ -0,0 +1,223 @@
+use leptos::*;
+use leptos_meta::*;
+
+use crate::app::{
+    components::{
+        use_wk_ctx, use_wk_state, ButtonSize, ButtonView, DescriptionView, IconView,
+        WorksheetHeader,
+    },
+    next_path_link,
+    process::{FixedProblemStatement, ValidationHintView, INPUT_CLASS},
+    state::{FieldKind, TemplateField, TemplateStep, WorkSheets},
+};
+
+/// step of a process template with the fields it defines
+#[component]
+pub fn CustomStepView(step: TemplateStep) -> impl IntoView {
+    let wk_ctx = use_wk_ctx();
+    let state = use_wk_state();
+
+    let title = t!(step.title().as_str()).to_string();
+    let link = next_path_link(step.path());
+
+    let report = {
+        let step = step.clone();
+        Signal::derive(move || {
+            let wk: WorkSheets = state.get().get();
+            step.report(&wk).unwrap_or_default()
+        })
+    };
+    let disable_cta = Signal::derive(move || !report.get().is_valid());
+
+    let fields = step
+        .fields
+        .iter()
+        .map(|field| view! { <CustomFieldView step_id=step.id.clone() field=field.clone()/> })
+        .collect_view();
+
+    view! {
+        <Title text={
+            let title = title.clone();
+            move || format!("{} | {} | {}", title, t!("process.title"), t!("name"))
+        }/>
+        <WorksheetHeader
+            title=title
+            description_id=step.id.clone()
+        />
+        <div class="grow w-full">
+            {step.description.clone().map(|description| view! {
+                <DescriptionView
+                    hidden=wk_ctx.description_hidden
+                    toggle_hidden=wk_ctx.toggle_description_hidden
+                >
+                    <p class="whitespace-pre-line">
+                        {t!(description.as_str()).to_string()}
+                    </p>
+                </DescriptionView>
+            })}
+            <form>
+                <FixedProblemStatement/>
+                {fields}
+            </form>
+            <div class="flex w-full mt-8 justify-center">
+                <ButtonView
+                    cta=2
+                    size=ButtonSize::Lg
+                    disabled={disable_cta}
+                    link
+                >
+                    {t!("worksheets.next_wk")}
+                </ButtonView>
+            </div>
+            <ValidationHintView report/>
+        </div>
+    }
+}
+
+/// input of a single template field, values are kept in the custom answers of the worksheets
+#[component]
+fn CustomFieldView(step_id: String, field: TemplateField) -> impl IntoView {
+    let state = use_wk_state();
+
+    let values = {
+        let step_id = step_id.clone();
+        let field_id = field.id.clone();
+        Signal::derive(move || {
+            let wk: WorkSheets = state.get().get();
+            wk.custom_answer(step_id.as_str(), field_id.as_str())
+                .map(|a| a.values.clone())
+                .unwrap_or_default()
+        })
+    };
+
+    let update = {
+        let step_id = step_id.clone();
+        let field_id = field.id.clone();
+        move |f: &dyn Fn(&mut Vec<String>)| {
+            state
+                .get_untracked()
+                .set_custom(step_id.as_str(), field_id.as_str(), |v| f(v))
+        }
+    };
+
+    let label = t!(field.label.as_str()).to_string();
+    let placeholder = field
+        .placeholder
+        .as_ref()
+        .map(|p| t!(p.as_str()).to_string())
+        .unwrap_or_default();
+    let anchor = format!("custom-{step_id}-{}", field.id);
+
+    let input = match field.kind {
+        FieldKind::Text => {
+            let update = update.clone();
+            view! {
+                <input
+                    type="text"
+                    class=INPUT_CLASS
+                    placeholder=placeholder
+                    prop:value=move || values.get().first().cloned().unwrap_or_default()
+                    on:input=move |ev| {
+                        let value = event_target_value(&ev);
+                        update(&|v| *v = vec![value.clone()]);
+                    }
+                />
+            }
+            .into_view()
+        }
+        FieldKind::Textarea => {
+            let update = update.clone();
+            view! {
+                <textarea
+                    class=INPUT_CLASS
+                    rows="4"
+                    placeholder=placeholder
+                    prop:value=move || values.get().first().cloned().unwrap_or_default()
+                    on:input=move |ev| {
+                        let value = event_target_value(&ev);
+                        update(&|v| *v = vec![value.clone()]);
+                    }
+                />
+            }
+            .into_view()
+        }
+        FieldKind::List => {
+            let on_add = {
+                let update = update.clone();
+                move |e: ev::MouseEvent| {
+                    e.prevent_default();
+                    update(&|v| v.push(String::default()));
+                }
+            };
+            let entry = {
+                let update = update.clone();
+                let placeholder = placeholder.clone();
+                move |i: usize| {
+                    let on_input = {
+                        let update = update.clone();
+                        move |ev: ev::Event| {
+                            let value = event_target_value(&ev);
+                            update(&|v| {
+                                if let Some(entry) = v.get_mut(i) {
+                                    *entry = value.clone();
+                                }
+                            });
+                        }
+                    };
+                    let on_remove = {
+                        let update = update.clone();
+                        move |e: ev::MouseEvent| {
+                            e.prevent_default();
+                            update(&|v| {
+                                if i < v.len() {
+                                    v.remove(i);
+                                }
+                            });
+                        }
+                    };
+                    view! {
+                        <li class="grid grid-cols-[1fr_auto] gap-2 items-center">
+                            <input
+                                type="text"
+                                class=INPUT_CLASS
+                                placeholder=placeholder.clone()
+                                prop:value=move || values.get().get(i).cloned().unwrap_or_default()
+                                on:input=on_input
+                            />
+                            <ButtonView
+                                cta=-1
+                                attr:title=t!("templates.remove_entry").to_string()
+                                attr:aria-label=t!("templates.remove_entry").to_string()
+                                on:click=on_remove
+                            >
+                                <IconView icon="Delete"/>
+                            </ButtonView>
+                        </li>
+                    }
+                }
+            };
+            view! {
+                <ol class="flex flex-col gap-4 mb-4">
+                    <For
+                        each=move || 0..values.get().len()
+                        key=|i| *i
+                        children=entry
+                    />
+                </ol>
+                <div class="flex justify-center">
+                    <ButtonView on:click=on_add>
+                        {t!("templates.add_entry")}
+                    </ButtonView>
+                </div>
+            }
+            .into_view()
+        }
+    };
+
+    view! {
+        <div id=anchor class="block mb-4 mt-6">
+            <h4 class="text-xl mb-4">{label}</h4>
+            {input}
+        </div>
+    }
+}
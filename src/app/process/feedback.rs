@@ -0,0 +1,146 @@
+#![cfg_attr(not(feature = "ssr"), allow(unused))]
+
+use form_signal::FormState;
+use leptos::*;
+use uuid::Uuid;
+
+use crate::app::components::{ButtonSize, ButtonView, IconView, StringInputView};
+
+#[cfg(feature = "ssr")]
+use crate::server::safe_error;
+#[cfg(feature = "ssr")]
+use spin_sdk::sqlite::{Connection, Value};
+
+/// stores whether the response of an inquery helped, with an optional comment
+#[server(RateInferrence, "/api")]
+pub async fn rate_inferrence(
+    id: Uuid,
+    helpful: bool,
+    comment: String,
+) -> Result<(), ServerFnError<String>> {
+    use super::{escape_text, QUESTION_CAP};
+
+    let comment = escape_text(comment.trim(), QUESTION_CAP);
+    let conn = Connection::open("default").map_err(safe_error)?;
+
+    // only complete responses to an inquery are rated
+    let sql = r#"
+        UPDATE inferrence
+        SET rating = ?, feedback = ?, rated_date = unixepoch()
+        WHERE id = ? AND done = 1 AND inquery_option IS NOT NULL
+        RETURNING id;
+"#;
+    let data = conn
+        .execute(
+            sql,
+            &[
+                Value::Integer(if helpful { 1 } else { -1 }),
+                if comment.is_empty() {
+                    Value::Null
+                } else {
+                    Value::Text(comment)
+                },
+                Value::Blob(Vec::from(id.as_bytes())),
+            ],
+        )
+        .map_err(safe_error)?;
+
+    if data.rows().next().is_some() {
+        Ok(())
+    } else {
+        Err(ServerFnError::Request("Not found".to_string()))
+    }
+}
+
+/// thumbs up or down for the response of an inquery
+#[component]
+pub fn InferrenceFeedbackView(id: Uuid) -> impl IntoView {
+    let rate_action = create_action(move |data: &(bool, String)| {
+        let (helpful, comment) = data.clone();
+        async move {
+            rate_inferrence(id, helpful, comment)
+                .await
+                .map_err(ServerFnErrorErr::from)
+        }
+    });
+    let pending = rate_action.pending();
+    let rated = Signal::derive(move || matches!(rate_action.value().get(), Some(Ok(_))));
+    let failed = Signal::derive(move || matches!(rate_action.value().get(), Some(Err(_))));
+
+    let helpful = create_rw_signal(None::<bool>);
+    let comment = create_rw_signal(FormState::new(String::default()));
+
+    let on_submit = move |e: ev::SubmitEvent| {
+        e.prevent_default();
+        if let Some(helpful) = helpful.get_untracked() {
+            let comment = comment.get_untracked().get_untracked();
+            rate_action.dispatch((helpful, comment));
+        }
+    };
+
+    let rate_button = move |value: bool| {
+        let label = if value {
+            t!("worksheets.inquire.feedback.helpful")
+        } else {
+            t!("worksheets.inquire.feedback.not_helpful")
+        }
+        .to_string();
+        view! {
+            <ButtonView
+                cta=Signal::derive(move || if helpful.get() == Some(value) { 2 } else { 0 })
+                size=ButtonSize::Sm
+                attr:title=label.clone()
+                attr:aria-label=label
+                attr:aria-pressed=move || (helpful.get() == Some(value)).to_string()
+                on:click=move |e| {
+                    e.prevent_default();
+                    helpful.set(Some(value));
+                }
+            >
+                {if value { "👍" } else { "👎" }}
+            </ButtonView>
+        }
+    };
+
+    view! {
+        <div class="mt-4 max-w-prose mx-auto" id="inquire-feedback">
+            <Show
+                when=move || !rated.get()
+                fallback=|| view! {
+                    <p class="text-sm opacity-80 text-center">
+                        {t!("worksheets.inquire.feedback.thanks")}
+                    </p>
+                }
+            >
+                <form on:submit=on_submit class="flex flex-col items-center gap-2">
+                    <p class="text-sm">{t!("worksheets.inquire.feedback.title")}</p>
+                    <div class="flex gap-4">
+                        {rate_button(true)}
+                        {rate_button(false)}
+                    </div>
+                    <Show when=move || helpful.get().is_some()>
+                        <StringInputView
+                            class="w-full"
+                            input_type="textarea"
+                            value=comment
+                            placeholder={t!("worksheets.inquire.feedback.placeholder").to_string()}
+                        />
+                        <ButtonView
+                            size=ButtonSize::Sm
+                            attr:type="submit"
+                            disabled=pending
+                        >
+                            <IconView icon="Send"/>
+                            {t!("worksheets.inquire.feedback.send")}
+                        </ButtonView>
+                    </Show>
+                    <Show when=move || failed.get()>
+                        <p class="text-sm opacity-80 text-center">
+                            {t!("worksheets.inquire.feedback.failed")}
+                        </p>
+                    </Show>
+                </form>
+            </Show>
+        </div>
+    }
+}
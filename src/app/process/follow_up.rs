@@ -0,0 +1,179 @@
+use form_signal::FormState;
+use leptos::*;
+use uuid::Uuid;
+
+use crate::app::{
+    components::{
+        use_wk_state, ButtonSize, ButtonView, IconView, Language, ReadOnlyView, Status, StatusView,
+        StringInputView,
+    },
+    state::{ChatMessage, ChatRole, WorkSheets},
+    tracking::SessionId,
+    use_lang,
+};
+
+use super::{inquire_follow_up, InferrenceStream, REJECTED};
+
+/// follow-up questions about the response of the inquire step
+#[component]
+pub fn FollowUpView() -> impl IntoView {
+    let state = use_wk_state();
+    let lang = use_lang();
+    let session_id = use_context::<SessionId>().unwrap();
+
+    let follow_up_action = create_action(|data: &(WorkSheets, Language, String, Option<Uuid>)| {
+        let (wk, lang, question, session_id) = data.clone();
+        async move {
+            inquire_follow_up(wk, lang, question, session_id)
+                .await
+                .map_err(ServerFnErrorErr::from)
+        }
+    });
+    let stream = InferrenceStream::new();
+    let streaming = stream.streaming();
+    let pending = Signal::derive(move || follow_up_action.pending().get() || streaming.get());
+
+    create_effect(move |_| match follow_up_action.value().get() {
+        Some(Ok(id)) => stream.start(id),
+        Some(Err(e)) => stream.fail(e),
+        None => {}
+    });
+
+    let thread = move || state.get_untracked().inquire.get_untracked().thread;
+
+    // the question joins the thread with its answer, consecutive user turns are never sent
+    let asked = create_rw_signal(None::<String>);
+
+    stream.on_done(move |text| {
+        if let Some(question) = asked.get_untracked() {
+            thread().update(|t| {
+                t.push(ChatMessage::new(ChatRole::User, question));
+                t.push(ChatMessage::new(ChatRole::Assistant, text));
+            });
+        }
+        asked.set(None);
+        stream.reset();
+    });
+
+    // the first message is the response to the inquery
+    let messages = Signal::derive(move || {
+        state
+            .get()
+            .inquire
+            .get()
+            .thread
+            .get()
+            .into_iter()
+            .skip(1)
+            .collect::<Vec<_>>()
+    });
+
+    let question = create_rw_signal(FormState::new(String::default()));
+
+    // a failed question is offered again
+    create_effect(move |_| {
+        if matches!(stream.response.get(), Some(Err(_))) {
+            if let Some(text) = asked.get_untracked() {
+                question.get_untracked().set(text);
+                asked.set(None);
+            }
+        }
+    });
+    let disable_ask =
+        Signal::derive(move || pending.get() || question.get().get().trim().is_empty());
+
+    let on_ask = move |e: ev::SubmitEvent| {
+        e.prevent_default();
+        let text = question.get_untracked().get_untracked().trim().to_string();
+        if text.is_empty() || pending.get_untracked() {
+            return;
+        }
+        // the question is sent separately from the thread
+        let wk: WorkSheets = state.get_untracked().get_untracked();
+        asked.set(Some(text.clone()));
+        question.get_untracked().set(String::default());
+        follow_up_action.dispatch((wk, lang.get_untracked(), text, session_id.0.get_untracked()));
+    };
+
+    view! {
+        <div class="mt-8" id="inquire-thread">
+            <h4 class="text-xl mb-4 w-full text-center">
+                {t!("worksheets.inquire.label_follow_up")}
+            </h4>
+            <ul class="flex flex-col gap-4 mb-4">
+                <For
+                    each=move || messages.get()
+                    key=|m| m.id
+                    let:message
+                >
+                    <li class=if message.is_user() { "self-end max-w-prose" } else { "" }>
+                        {if message.is_user() {
+                            view! {
+                                <p class="whitespace-pre-line p-2 rounded bg-stone-200 dark:bg-stone-800">
+                                    {message.text}
+                                </p>
+                            }.into_view()
+                        } else {
+                            view! { <ReadOnlyView>{message.text.clone()}</ReadOnlyView> }.into_view()
+                        }}
+                    </li>
+                </For>
+                {move || asked.get().map(|text| view! {
+                    <li class="self-end max-w-prose">
+                        <p class="whitespace-pre-line p-2 rounded bg-stone-200 dark:bg-stone-800">
+                            {text}
+                        </p>
+                    </li>
+                })}
+            </ul>
+            {move || match stream.response.get() {
+                Some(Ok(chunk)) if chunk.text.is_empty() => view! {
+                    <StatusView status=Status::Pending attr:class="mb-4 mx-auto"/>
+                }.into_view(),
+                Some(Ok(chunk)) => view! {
+                    <ReadOnlyView>{chunk.text.clone()}</ReadOnlyView>
+                }.into_view(),
+                Some(Err(ServerFnErrorErr::Request(e))) if e.starts_with(REJECTED) => {
+                    let phrase = e.trim_start_matches(REJECTED).to_string();
+                    view! {
+                        <p class="text-sm opacity-80 text-center">
+                            {t!("worksheets.inquire.rejected", phrase = phrase)}
+                        </p>
+                    }.into_view()
+                }
+                Some(Err(e)) => view! {
+                    <p class="text-sm opacity-80 text-center">{e.to_string()}</p>
+                }.into_view(),
+                None if follow_up_action.pending().get() => view! {
+                    <StatusView status=Status::Pending attr:class="mb-4 mx-auto"/>
+                }.into_view(),
+                None => ().into_view(),
+            }}
+            <Show when=move || streaming.get()>
+                <div class="flex w-full mt-4 justify-center">
+                    <ButtonView cta=1 on:click=move |_| stream.stop()>
+                        <IconView icon="Close"/>
+                        {t!("worksheets.inquire.stop")}
+                    </ButtonView>
+                </div>
+            </Show>
+            <form on:submit=on_ask class="mt-4">
+                <StringInputView
+                    input_type="textarea"
+                    value=question
+                    placeholder={t!("worksheets.inquire.placeholder_follow_up").to_string()}
+                />
+                <div class="flex justify-center mt-2">
+                    <ButtonView
+                        size=ButtonSize::Sm
+                        attr:type="submit"
+                        disabled=disable_ask
+                    >
+                        <IconView icon="Send"/>
+                        {t!("worksheets.inquire.ask")}
+                    </ButtonView>
+                </div>
+            </form>
+        </div>
+    }
+}
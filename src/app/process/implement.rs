@@ -1,4 +1,3 @@
-use form_signal::FormState;
 use leptos::*;
 use leptos_meta::*;
 use leptos_router::*;
@@ -7,15 +6,16 @@ use web_time::Instant;
 
 use crate::app::{
     components::{
-        use_example_ctx, use_wk_ctx, use_wk_state, ButtonSize, ButtonView, DescriptionView,
-        DragListCtx, HistoryEntry, ListInputView, ReadOnlyListView, ReadOnlyView, UndoRemove,
-        WorksheetHeader,
+        use_example_ctx, use_example_shared, use_wk_ctx, use_wk_state, ButtonSize, ButtonView,
+        DescriptionView, DragListCtx, HistoryEntry, ListInputView, ReadOnlyListView, ReadOnlyView,
+        UndoRemove, WorksheetHeader,
     },
+    next_step_link,
     process::{
         FixedProblemStatement, FixedQuestionStatement, FixedSolutionsChoice,
-        FixedStakeholdersChoice,
+        FixedStakeholdersChoice, SuggestTarget, SuggestView,
     },
-    state::{Completenes, ProcessStep},
+    state::{Completenes, ImplementWKFormState, KeyedEntry, ProcessStep},
     tabs_signal, use_lang,
 };
 
@@ -24,8 +24,7 @@ use crate::app::{
 pub fn ImplementView() -> impl IntoView {
     let wk_state = use_wk_state();
     let wk_ctx = use_wk_ctx();
-    let lang = use_lang();
-    let link = Signal::derive(move || format!("/{}/process/5", lang.get()));
+    let link = next_step_link(ProcessStep::Implement);
 
     let now_delete_history = create_rw_signal(vec![]);
     let nows_data = Signal::derive(move || {
@@ -37,7 +36,7 @@ pub fn ImplementView() -> impl IntoView {
             .unwrap_or_default()
     });
     let nows_value_add = move |(next, index): (String, Option<usize>)| {
-        let next = FormState::new(next);
+        let next = ImplementWKFormState::now_entry(next);
         let id = next.id;
         wk_state.get().implement.update(move |p| {
             p.now.insert(index.unwrap_or(p.now.len()), next);
@@ -53,11 +52,8 @@ pub fn ImplementView() -> impl IntoView {
     };
     let now_restore = move |(val, at, _): HistoryEntry<String>| {
         wk_state.get().implement.update(move |p| {
-            if p.now.len() >= at {
-                p.now.insert(at, FormState::new(val));
-            } else {
-                p.now.push(FormState::new(val));
-            }
+            let entry = ImplementWKFormState::now_entry(val);
+            p.now.insert(at.min(p.now.len()), entry);
         })
     };
 
@@ -71,7 +67,7 @@ pub fn ImplementView() -> impl IntoView {
             .unwrap_or_default()
     });
     let bests_value_add = move |(next, index): (String, Option<usize>)| {
-        let next = FormState::new(next);
+        let next = ImplementWKFormState::best_entry(next);
         let id = next.id;
         wk_state.get().implement.update(move |p| {
             p.best.insert(index.unwrap_or(p.best.len()), next);
@@ -87,11 +83,8 @@ pub fn ImplementView() -> impl IntoView {
     };
     let best_restore = move |(val, at, _): HistoryEntry<String>| {
         wk_state.get().implement.update(move |p| {
-            if p.best.len() >= at {
-                p.best.insert(at, FormState::new(val));
-            } else {
-                p.best.push(FormState::new(val));
-            }
+            let entry = ImplementWKFormState::best_entry(val);
+            p.best.insert(at.min(p.best.len()), entry);
         })
     };
 
@@ -100,15 +93,24 @@ pub fn ImplementView() -> impl IntoView {
     let disable_cta = Signal::derive(move || !wk_state.get().implement.get().get().is_complete());
 
     DragListCtx::provide(Callback::new(
-        move |(entry, list_name, insert_after): (FormState<String>, String, Uuid)| {
+        move |((id, text), list_name, insert_after): (KeyedEntry, String, Uuid)| {
             let wk = wk_state.get().implement;
 
             wk.update(|wk| {
                 match list_name.as_str() {
                     "now" => {
-                        let old_pos = wk.now.iter().position(|f| f.id == entry.id);
-                        wk.now.retain(|f| f.id != entry.id);
-                        wk.best.retain(|f| f.id != entry.id);
+                        let old_pos = wk.now.iter().position(|f| f.id == id);
+                        // entries from the other list take the validators of this one
+                        let entry = match old_pos {
+                            Some(pos) => wk.now[pos].clone(),
+                            None => {
+                                let mut moved = ImplementWKFormState::now_entry(text);
+                                moved.id = id;
+                                moved
+                            }
+                        };
+                        wk.now.retain(|f| f.id != id);
+                        wk.best.retain(|f| f.id != id);
                         let new_pos = wk.now.iter().position(|f| f.id == insert_after);
 
                         if let Some(pos) = new_pos.map(|p| p + 1).or(old_pos) {
@@ -118,9 +120,18 @@ pub fn ImplementView() -> impl IntoView {
                         }
                     }
                     "best" => {
-                        let old_pos = wk.best.iter().position(|f| f.id == entry.id);
-                        wk.now.retain(|f| f.id != entry.id);
-                        wk.best.retain(|f| f.id != entry.id);
+                        let old_pos = wk.best.iter().position(|f| f.id == id);
+                        // entries from the other list take the validators of this one
+                        let entry = match old_pos {
+                            Some(pos) => wk.best[pos].clone(),
+                            None => {
+                                let mut moved = ImplementWKFormState::best_entry(text);
+                                moved.id = id;
+                                moved
+                            }
+                        };
+                        wk.now.retain(|f| f.id != id);
+                        wk.best.retain(|f| f.id != id);
                         let new_pos = wk.best.iter().position(|f| f.id == insert_after);
 
                         if let Some(pos) = new_pos.map(|p| p + 1).or(old_pos) {
@@ -165,7 +176,7 @@ pub fn ImplementView() -> impl IntoView {
                 <FixedStakeholdersChoice/>
                 <FixedQuestionStatement/>
                 <div class="grid lg:grid-cols-2 text-center mt-8 mb-4 gap-6">
-                    <div>
+                    <div id="implement-now">
                         <h4 class="text-xl mb-2">
                             {t!("worksheets.implement.label_now")}
                         </h4>
@@ -179,8 +190,14 @@ pub fn ImplementView() -> impl IntoView {
                             placeholder={t!("worksheets.implement.placeholder_now").to_string()}
                             drop_target_name="now"
                         />
+                        <SuggestView
+                            target=SuggestTarget::Now
+                            on_accept=move |value: String| {
+                                nows_value_add((value, None));
+                            }
+                        />
                     </div>
-                    <div>
+                    <div id="implement-best">
                         <h4 class="text-xl mb-2">
                             {t!("worksheets.implement.label_best")}
                         </h4>
@@ -194,6 +211,12 @@ pub fn ImplementView() -> impl IntoView {
                             placeholder={t!("worksheets.implement.placeholder_best").to_string()}
                             drop_target_name="best"
                         />
+                        <SuggestView
+                            target=SuggestTarget::Best
+                            on_accept=move |value: String| {
+                                bests_value_add((value, None));
+                            }
+                        />
                     </div>
                 </div>
             </form>
@@ -277,9 +300,14 @@ pub fn FixedBestList() -> impl IntoView {
 pub fn ExampleImplementView() -> impl IntoView {
     let lang = use_lang();
     let (wk, example) = use_example_ctx();
+    let shared = use_example_shared();
     let wk_ctx = use_wk_ctx();
 
-    let tabs = tabs_signal(ProcessStep::Implement);
+    let tabs = if shared {
+        Signal::derive(Vec::new)
+    } else {
+        tabs_signal(ProcessStep::Implement)
+    };
 
     let question_statement = Signal::derive(move || wk.get().compromise.question);
     let problem_statement = Signal::derive(move || wk.get().problem.problem_statement);
@@ -318,9 +346,11 @@ pub fn ExampleImplementView() -> impl IntoView {
                 <p class="whitespace-pre-line">
                     {move || example.get().description}
                 </p>
-                <A href=case_href attr:class="underline">
-                    {move || t!("worksheets.view_example", title=example.get().title)}
-                </A>
+                <Show when=move || !shared>
+                    <A href=case_href attr:class="underline">
+                        {move || t!("worksheets.view_example", title=example.get().title)}
+                    </A>
+                </Show>
             </DescriptionView>
             <div role="form">
                 <div class="max-w-prose mb-4 whitespace-pre-line italic">
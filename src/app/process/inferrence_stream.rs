@@ -0,0 +1,125 @@
+use leptos::*;
+use uuid::Uuid;
+
+use super::{inquire_inferrence_chunk, stop_inferrence, InferrenceChunk};
+
+pub type InferrenceResult = Result<InferrenceChunk, ServerFnErrorErr<String>>;
+
+/// response of a stored inferrence, requested chunk by chunk until it is done or stopped
+#[derive(Clone, Copy)]
+pub struct InferrenceStream {
+    pub response: RwSignal<Option<InferrenceResult>>,
+    id: RwSignal<Option<Uuid>>,
+    chunk_action: Action<Uuid, Result<(Uuid, InferrenceChunk), ServerFnErrorErr<String>>>,
+    stop_action: Action<Uuid, Result<(), ServerFnErrorErr<String>>>,
+}
+
+impl Default for InferrenceStream {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InferrenceStream {
+    pub fn new() -> Self {
+        let chunk_action = create_action(|id: &Uuid| {
+            let id = *id;
+            async move {
+                inquire_inferrence_chunk(id)
+                    .await
+                    .map(|chunk| (id, chunk))
+                    .map_err(ServerFnErrorErr::from)
+            }
+        });
+        let stop_action = create_action(|id: &Uuid| {
+            let id = *id;
+            async move { stop_inferrence(id).await.map_err(ServerFnErrorErr::from) }
+        });
+
+        let id = create_rw_signal(None::<Uuid>);
+        let response = create_rw_signal(None::<InferrenceResult>);
+
+        create_effect(move |_| match chunk_action.value().get() {
+            // chunks of a stopped inferrence are ignored
+            Some(Ok((chunk_id, chunk))) if id.get_untracked() == Some(chunk_id) => {
+                let done = chunk.done;
+                response.set(Some(Ok(chunk)));
+                if done {
+                    id.set(None);
+                } else {
+                    chunk_action.dispatch(chunk_id);
+                }
+            }
+            Some(Err(e)) => {
+                id.set(None);
+                response.set(Some(Err(e)));
+            }
+            _ => {}
+        });
+
+        Self {
+            response,
+            id,
+            chunk_action,
+            stop_action,
+        }
+    }
+
+    pub fn start(&self, id: Uuid) {
+        self.id.set(Some(id));
+        self.response.set(Some(Ok(InferrenceChunk::default())));
+        self.chunk_action.dispatch(id);
+    }
+
+    pub fn fail(&self, e: ServerFnErrorErr<String>) {
+        self.id.set(None);
+        self.response.set(Some(Err(e)));
+    }
+
+    /// keeps the response generated so far
+    pub fn stop(&self) {
+        if let Some(id) = self.id.get_untracked() {
+            self.id.set(None);
+            self.response.update(|r| {
+                if let Some(Ok(chunk)) = r.as_mut() {
+                    chunk.done = true;
+                }
+            });
+            self.stop_action.dispatch(id);
+        }
+    }
+
+    /// discards the response
+    pub fn reset(&self) {
+        if let Some(id) = self.id.get_untracked() {
+            self.id.set(None);
+            self.stop_action.dispatch(id);
+        }
+        self.response.set(None);
+    }
+
+    /// whether the response is still being generated
+    pub fn streaming(&self) -> Signal<bool> {
+        let response = self.response;
+        Signal::derive(move || {
+            response
+                .get()
+                .map(|r| r.is_ok_and(|c| !c.done))
+                .unwrap_or_default()
+        })
+    }
+
+    /// text of a response which is done, once
+    pub fn on_done(&self, f: impl Fn(String) + 'static) {
+        let response = self.response;
+        create_effect(move |was_done: Option<bool>| {
+            let done = response.with(|r| matches!(r, Some(Ok(c)) if c.done));
+            if done && was_done == Some(false) {
+                if let Some(Some(Ok(chunk))) = response.try_get_untracked() {
+                    f(chunk.text);
+                }
+            }
+            done
+        });
+    }
+}
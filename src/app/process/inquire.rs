@@ -6,16 +6,19 @@ use uuid::Uuid;
 use crate::app::{
     components::{
         use_wk_ctx, use_wk_state, ButtonSize, ButtonView, CheckboxInputView, CheckedOption,
-        ContactForm, DescriptionView, ErrorView, IconView, RadioInputView, ReadOnlyView, Status,
-        StatusView, StringInputView, WorksheetHeader,
+        ContactForm, DescriptionView, ErrorView, IconView, Language, RadioInputView, ReadOnlyView,
+        Status, StatusView, StringInputView, WorksheetHeader,
     },
-    process::inquire_personal,
-    state::{Completenes, InqueryOption, InquireWK, WorkSheets},
+    process::{inquire_personal, PromptPreviewView, ValidationHintView, REJECTED},
+    state::{use_store, ChatMessage, ChatRole, Completenes, InqueryOption, InquireWK, WorkSheets},
     tracking::SessionId,
     use_lang,
 };
 
-use super::inquire_inferrence;
+use super::{
+    inquire_inferrence, FollowUpView, InferrenceChunk, InferrenceFeedbackView, InferrenceStream,
+    SuggestedEditsView,
+};
 
 /// step 7
 #[component]
@@ -23,15 +26,43 @@ pub fn InquireView() -> impl IntoView {
     let state = use_wk_state();
     let wk_ctx = use_wk_ctx();
     let session_id = use_context::<SessionId>().unwrap();
+    let lang = use_lang();
 
-    let inquire_action = create_action(|data: &(WorkSheets, Option<Uuid>)| {
-        let (wk, session_id) = data.clone();
+    let inquire_action = create_action(|data: &(WorkSheets, Language, Option<Uuid>, bool)| {
+        let (wk, lang, session_id, regenerate) = data.clone();
         async move {
-            inquire_inferrence(wk, session_id)
+            inquire_inferrence(wk, lang, session_id, regenerate)
                 .await
                 .map_err(|e| ServerFnErrorErr::from(e))
         }
     });
+    let stream = InferrenceStream::new();
+
+    create_effect(move |_| match inquire_action.value().get() {
+        Some(Ok(id)) => stream.start(id),
+        Some(Err(e)) => stream.fail(e),
+        None => {}
+    });
+
+    // the response starts the follow-up thread, kept with the workbook
+    stream.on_done(move |text| {
+        state
+            .get_untracked()
+            .inquire
+            .get_untracked()
+            .thread
+            .set(vec![ChatMessage::new(ChatRole::Assistant, text)]);
+    });
+
+    let thread = Signal::derive(move || state.get().inquire.get().thread.get());
+    // only known for a response requested in this session
+    let inferrence_id = Signal::derive(move || inquire_action.value().get().and_then(|r| r.ok()));
+
+    let on_new_inquery = Callback::new(move |_| {
+        stream.reset();
+        inquire_action.value().set(None);
+        state.get().inquire.get().thread.set(vec![]);
+    });
     let inquire_personal_action = create_action(|data: &(WorkSheets, Option<Uuid>)| {
         let (wk, session_id) = data.clone();
         let contact = wk.inquire.contact.clone();
@@ -81,13 +112,8 @@ pub fn InquireView() -> impl IntoView {
 
     let contact_value = Signal::derive(move || state.get().inquire.get().contact.get());
 
-    let on_submit = Callback::new(move |_| {
-        let inquery = state.get().inquire.get().get();
+    let inquire = move |regenerate: bool| {
         let wk = state.get().get();
-        let session_id = session_id.0.get();
-        if inquery.personalized {
-            inquire_personal_action.dispatch((wk.clone(), session_id.clone()));
-        }
         inquire_action.dispatch((
             WorkSheets {
                 inquire: InquireWK {
@@ -96,14 +122,34 @@ pub fn InquireView() -> impl IntoView {
                 },
                 ..wk
             },
-            session_id,
+            lang.get_untracked(),
+            session_id.0.get(),
+            regenerate,
         ));
+    };
+
+    let on_submit = Callback::new(move |_| {
+        let inquery = state.get().inquire.get().get();
+        if inquery.personalized {
+            inquire_personal_action.dispatch((state.get().get(), session_id.0.get()));
+        }
+        inquire(false);
     });
 
-    let disable_inquire = Signal::derive(move || {
+    // the same inquery is answered from the cache unless it is regenerated
+    let on_regenerate = Callback::new(move |_| {
+        on_new_inquery.call(());
+        inquire(true);
+    });
+
+    let store = use_store();
+    let report = Signal::derive(move || {
         let wk = state.get().get();
-        !wk.is_complete() || !wk.inquire.is_complete()
+        let mut report = store.get().template().validate(&wk);
+        report.extend(wk.inquire.validate());
+        report
     });
+    let disable_inquire = Signal::derive(move || !report.get().is_valid());
 
     view! {
         <Title text={move || format!("{} | {} | {}", t!("worksheets.inquire.title"), t!("process.title"), t!("name"))}/>
@@ -113,9 +159,13 @@ pub fn InquireView() -> impl IntoView {
         />
         <div class="grow w-full">
             <Show
-                when={move || inquire_action.value().get().is_none() && !inquire_action.pending().get()}
+                when={move || {
+                    stream.response.get().is_none()
+                        && thread.get().is_empty()
+                        && !inquire_action.pending().get()
+                }}
                 fallback=move || view!{
-                    <InquireResult inquire_action inquire_personal_action/>
+                    <InquireResult stream inferrence_id on_new_inquery on_regenerate inquire_personal_action/>
                 }
             >
                 <DescriptionView
@@ -131,18 +181,20 @@ pub fn InquireView() -> impl IntoView {
 
                     on_submit.call(())
                 }>
-                    <div class="max-w-prose mb-4 whitespace-pre-line">
+                    <div class="max-w-prose mb-4 whitespace-pre-line" id="inquire-inquery_option">
                         <p>{t!("worksheets.inquire.instruction_1")}</p>
                     </div>
                     <RadioInputView options=inquery_options value=prompt_option />
                     <Show when=move || show_prompt_input.get()>
-                        <StringInputView
-                            class="mt-2"
-                            attr:required=true
-                            input_type="textarea"
-                            value=custom_prompt
-                            placeholder={t!("worksheets.inquire.placeholder").to_string()}
-                        />
+                        <div id="inquire-custom_prompt">
+                            <StringInputView
+                                class="mt-2"
+                                attr:required=true
+                                input_type="textarea"
+                                value=custom_prompt
+                                placeholder={t!("worksheets.inquire.placeholder").to_string()}
+                            />
+                        </div>
                     </Show>
                     <hr class="border-t border-slate-400 mt-4 mb-8" />
                     <CheckboxInputView option=share_option value=share_value />
@@ -164,7 +216,11 @@ pub fn InquireView() -> impl IntoView {
                             {t!("worksheets.inquire.cta")}
                         </ButtonView>
                     </div>
+                    <ValidationHintView report/>
                 </form>
+                {cfg!(debug_assertions).then(|| view! {
+                    <PromptPreviewView wk=Signal::derive(move || state.get().get())/>
+                })}
             </Show>
         </div>
     }
@@ -172,7 +228,10 @@ pub fn InquireView() -> impl IntoView {
 
 #[component]
 fn InquireResult(
-    inquire_action: Action<(WorkSheets, Option<Uuid>), Result<String, ServerFnErrorErr<String>>>,
+    stream: InferrenceStream,
+    inferrence_id: Signal<Option<Uuid>>,
+    on_new_inquery: Callback<()>,
+    on_regenerate: Callback<()>,
     inquire_personal_action: Action<
         (WorkSheets, Option<Uuid>),
         Result<(), ServerFnErrorErr<String>>,
@@ -180,15 +239,45 @@ fn InquireResult(
 ) -> impl IntoView {
     let state = use_wk_state();
     let pending_personal = inquire_personal_action.pending();
-    let pending = inquire_action.pending();
     let done_personal = inquire_personal_action.value();
-    let response = inquire_action.value();
+    let thread = Signal::derive(move || state.get().inquire.get().thread.get());
+    // a stored response is shown once the stream is gone
+    let response = Signal::derive(move || {
+        stream.response.get().or_else(|| {
+            thread.get().first().map(|m| {
+                Ok(InferrenceChunk {
+                    text: m.text.clone(),
+                    done: true,
+                    ..Default::default()
+                })
+            })
+        })
+    });
+    let pending = Signal::derive(move || {
+        response
+            .get()
+            .map(|r| r.is_ok_and(|c| c.text.is_empty() && !c.done))
+            .unwrap_or(true)
+    });
+    // the question of the user was refused as an injection attempt
+    let rejected = Signal::derive(
+        move || matches!(response.get(), Some(Err(ServerFnErrorErr::Request(e))) if e.starts_with(REJECTED)),
+    );
+    let streaming = stream.streaming();
+    let done = Signal::derive(move || {
+        response
+            .get()
+            .map(|r| r.is_ok_and(|c| c.done))
+            .unwrap_or_default()
+    });
     let lang = use_lang();
     let link = Signal::derive(move || format!("/{}/process/1", lang.get()));
     let (last_chance, set_last_chance) = create_signal(false);
     let contact_value = Signal::derive(move || state.get().inquire.get().contact.get());
-    let send_disabled =
-        Signal::derive(move || !state.get().inquire.get().contact.get().get().is_complete());
+    let send_disabled = Signal::derive(move || {
+        let contact = state.get().inquire.get().contact.get();
+        !contact.get().is_complete() || !contact.check()
+    });
     let session_id = use_context::<SessionId>().unwrap();
 
     let submit = move |e: ev::SubmitEvent| {
@@ -212,21 +301,67 @@ fn InquireResult(
                         attr:class="mb-4 mx-auto"
                     />
                 }.into_view()
+            } else if let Some(Err(ServerFnErrorErr::Request(e))) = response.get().filter(|_| rejected.get()) {
+                let phrase = e.trim_start_matches(REJECTED).to_string();
+                view!{
+                    <p class="max-w-prose mx-auto text-center mb-4">
+                        {t!("worksheets.inquire.rejected", phrase = phrase)}
+                    </p>
+                }.into_view()
             } else if let Some(r) = response.get() {
+                let flagged = r.as_ref().is_ok_and(|c| !c.flags.is_empty());
+                // fields of the workbook left out to fit the prompt
+                let trimmed = r
+                    .as_ref()
+                    .map(|c| {
+                        c.trimmed
+                            .iter()
+                            .map(|f| t!(format!("worksheets.fields.{}", f.replace('.', "_")).as_str()).to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                    .unwrap_or_default();
+                let text = r.map(|c| c.text);
                 view!{
                     <StatusView
                         status=Status::Success
                         message={t!("worksheets.inquire.ai_disclaimer").to_string()}
                         attr:class="mb-4 mx-auto"
                     />
+                    <Show when=move || flagged>
+                        <p class="text-sm opacity-80 text-center mb-4">
+                            {t!("worksheets.inquire.flagged")}
+                        </p>
+                    </Show>
+                    {(!trimmed.is_empty()).then(|| view! {
+                        <p class="text-sm opacity-80 text-center mb-4">
+                            {t!("worksheets.inquire.trimmed", fields = trimmed)}
+                        </p>
+                    })}
                     <ReadOnlyView>
-                        {r.clone()}
+                        {text.clone()}
                     </ReadOnlyView>
-                    <hr class="border-t border-slate-400 mt-4 mb-8"/>
+                    <Show when=move || streaming.get()>
+                        <div class="flex w-full mt-4 justify-center">
+                            <ButtonView
+                                cta=1
+                                on:click=move |_| stream.stop()
+                            >
+                                <IconView icon="Close"/>
+                                {t!("worksheets.inquire.stop")}
+                            </ButtonView>
+                        </div>
+                    </Show>
                 }.into_view()
             } else {
                 ().into_view()
             }}
+            <Show when=move || done.get()>
+                {move || inferrence_id.get().map(|id| view! { <InferrenceFeedbackView id/> })}
+                <SuggestedEditsView/>
+                <FollowUpView/>
+            </Show>
+            <hr class="border-t border-slate-400 mt-4 mb-8"/>
             {move || if pending_personal.get() {
                 view!{
                     <StatusView
@@ -267,7 +402,7 @@ fn InquireResult(
                     </div>
                 </form>
             </Show>
-            <Show when={move|| !pending_personal.get() && !pending.get()}>
+            <Show when={move|| !pending_personal.get() && !streaming.get()}>
                 <div class="flex w-full mt-8 gap-8 justify-center">
                     <ButtonView
                         cta=1
@@ -277,6 +412,22 @@ fn InquireResult(
                         <IconView icon="Restart"/>
                         {t!("worksheets.inquire.cta_2")}
                     </ButtonView>
+                    <ButtonView
+                        cta=1
+                        size=ButtonSize::Lg
+                        on:click=move |_| on_new_inquery.call(())
+                    >
+                        <IconView icon="Send"/>
+                        {t!("worksheets.inquire.new_inquery")}
+                    </ButtonView>
+                    <ButtonView
+                        cta=1
+                        size=ButtonSize::Lg
+                        on:click=move |_| on_regenerate.call(())
+                    >
+                        <IconView icon="Restart"/>
+                        {t!("worksheets.inquire.regenerate")}
+                    </ButtonView>
                     <Show when={move || done_personal.get().is_none() && !last_chance.get()}>
                         <ButtonView
                             cta=2
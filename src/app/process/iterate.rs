@@ -1,4 +1,3 @@
-use form_signal::FormState;
 use leptos::*;
 use leptos_meta::*;
 use leptos_router::use_navigate;
@@ -7,14 +6,16 @@ use web_time::Instant;
 
 use crate::app::{
     components::{
-        use_wk_ctx, use_wk_state, ButtonSize, ButtonView, DescriptionView, HistoryEntry, IconView,
-        ListInputView, ModalView, Status, StatusView, StringInputView, UndoRemove, WorksheetHeader,
+        use_wk_ctx, use_wk_state, ButtonSize, ButtonView, DateInputView, DescriptionView,
+        HistoryEntry, IconView, ListInputView, ModalView, Status, StatusView, StringInputView,
+        UndoRemove, WorksheetHeader,
     },
     process::{
-        FixedBestList, FixedNowList, FixedProblemStatement, FixedQuestionStatement,
-        FixedSolutionsChoice, FixedStakeholdersChoice,
+        CalendarExportView, DocumentExportView, FixedBestList, FixedNowList, FixedProblemStatement,
+        FixedQuestionStatement, FixedSolutionsChoice, FixedStakeholdersChoice, MarkdownExportView,
+        MilestonesView, ShareView, ValidationHintView,
     },
-    state::Completenes,
+    state::{use_store, IterateWKFormState},
     use_lang,
 };
 
@@ -36,7 +37,7 @@ pub fn IterateView() -> impl IntoView {
     let externals_delete_history = create_rw_signal(vec![]);
 
     let externals_value_add = move |(next, index): (String, Option<usize>)| {
-        let next = FormState::new(next);
+        let next = IterateWKFormState::external_resources_entry(next);
         let id = next.id;
         state.get().iterate.update(move |p| {
             p.external_resources
@@ -45,7 +46,7 @@ pub fn IterateView() -> impl IntoView {
         id
     };
     let resources_value_add = move |(next, index): (String, Option<usize>)| {
-        let next = FormState::new(next);
+        let next = IterateWKFormState::resources_entry(next);
         let id = next.id;
         state.get().iterate.update(move |p| {
             p.resources.insert(index.unwrap_or(p.resources.len()), next);
@@ -72,36 +73,36 @@ pub fn IterateView() -> impl IntoView {
     };
     let externals_restore = move |(val, at, _): HistoryEntry<String>| {
         state.get().iterate.update(move |p| {
-            if p.external_resources.len() >= at {
-                p.external_resources.insert(at, FormState::new(val));
-            } else {
-                p.external_resources.push(FormState::new(val));
-            }
+            let entry = IterateWKFormState::external_resources_entry(val);
+            p.external_resources
+                .insert(at.min(p.external_resources.len()), entry);
         })
     };
     let resources_restore = move |(val, at, _): HistoryEntry<String>| {
         state.get().iterate.update(move |p| {
-            if p.resources.len() >= at {
-                p.resources.insert(at, FormState::new(val));
-            } else {
-                p.resources.push(FormState::new(val));
-            }
+            let entry = IterateWKFormState::resources_entry(val);
+            p.resources.insert(at.min(p.resources.len()), entry);
         })
     };
 
-    let min_start = Signal::derive(move || chrono::Local::now().date_naive().to_string());
+    let min_start = Signal::derive(move || Some(chrono::Local::now().date_naive()));
+    // the deadline comes after the start
     let min_end = Signal::derive(move || {
-        let start = start_date.get().get();
-        if start.is_empty() {
-            min_start.get()
-        } else {
-            start
-        }
+        start_date
+            .get()
+            .get()
+            .and_then(|start| start.succ_opt())
+            .or(min_start.get())
     });
 
     let (show_download, set_show_download) = create_signal(false);
 
-    let disable_download = Signal::derive(move || !state.get().get().is_complete());
+    let store = use_store();
+    let report = Signal::derive(move || {
+        let wk = state.get().get();
+        store.get().template().validate(&wk)
+    });
+    let disable_download = Signal::derive(move || !report.get().is_valid());
 
     let on_download = move |_| {
         set_show_download.set(true);
@@ -141,7 +142,7 @@ pub fn IterateView() -> impl IntoView {
                 <div class="max-w-prose mb-4 mt-6 whitespace-pre-line">
                     <p>{t!("worksheets.iterate.instruction_title")}</p>
                 </div>
-                <label class="block mb-4 mt-2">
+                <label class="block mb-4 mt-2" id="iterate-title">
                     <p class="mb-2">{t!("worksheets.iterate.label_title")}</p>
                     <StringInputView
                         input_type="text"
@@ -153,7 +154,7 @@ pub fn IterateView() -> impl IntoView {
                     <p>{t!("worksheets.iterate.instruction_resources")}</p>
                 </div>
                 <div class="grid lg:grid-cols-2 gap-6 mb-4">
-                    <div>
+                    <div id="iterate-resources">
                         <h4 class="text-xl mb-4 w-full text-center">
                             {t!("worksheets.iterate.label_resources")}
                         </h4>
@@ -184,25 +185,25 @@ pub fn IterateView() -> impl IntoView {
                     <p>{t!("worksheets.iterate.instruction_dates")}</p>
                 </div>
                 <div class="grid lg:grid-cols-2 gap-6 mb-4">
-                    <label>
+                    <label id="iterate-start_date">
                         <p class="mb-2">{t!("worksheets.iterate.label_date_1")}</p>
-                        <StringInputView
-                            input_type="date"
+                        <DateInputView
                             value=start_date
-                            attr:min=min_start
-                            placeholder={t!("worksheets.iterate.placeholder_date_1").to_string()}
+                            min=min_start
                         />
                     </label>
-                    <label>
+                    <label id="iterate-end_date">
                         <p class="mb-2">{t!("worksheets.iterate.label_date_2")}</p>
-                        <StringInputView
-                            input_type="date"
+                        <DateInputView
                             value=end_date
-                            attr:min=min_end
-                            placeholder={t!("worksheets.iterate.placeholder_date_2").to_string()}
+                            min=min_end
                         />
                     </label>
                 </div>
+                <div class="max-w-prose mb-4 whitespace-pre-line">
+                    <p>{t!("worksheets.iterate.instruction_milestones")}</p>
+                </div>
+                <MilestonesView/>
             </form>
             <div class="flex w-full mt-8 justify-center">
                 <ButtonView
@@ -215,6 +216,13 @@ pub fn IterateView() -> impl IntoView {
                     {t!("worksheets.iterate.cta")}
                 </ButtonView>
             </div>
+            <ValidationHintView report/>
+            <MarkdownExportView attr:class="mt-4"/>
+            <div class="flex flex-wrap w-full mt-4 gap-4 justify-center">
+                <DocumentExportView/>
+                <CalendarExportView/>
+                <ShareView/>
+            </div>
         </div>
         <ModalView
             curtain=true
@@ -0,0 +1,144 @@
+use form_signal::FormState;
+use leptos::*;
+use uuid::Uuid;
+
+use crate::app::{
+    components::{use_wk_state, ButtonView, DateInputView, IconView},
+    state::{IterateWK, Milestone},
+};
+
+pub const INPUT_CLASS: &str = "w-full px-4 py-2 rounded border border-slate-400 bg-stone-50 dark:bg-stone-950 text-stone-950 dark:text-stone-50 text-lg focus:outline-purple-400 focus:outline";
+
+/// dated milestones of the iteration
+#[component]
+pub fn MilestonesView() -> impl IntoView {
+    let state = use_wk_state();
+
+    let iterate = Signal::derive(move || {
+        let iterate: IterateWK = (&state.get().iterate.get()).into();
+        iterate
+    });
+    let milestones = Signal::derive(move || iterate.get().milestones);
+
+    let update = move |id: Uuid, f: &dyn Fn(&mut Milestone)| {
+        state
+            .get_untracked()
+            .iterate
+            .get_untracked()
+            .milestones
+            .update(|m| m.iter_mut().filter(|m| m.id == id).for_each(f));
+    };
+
+    let on_add = move |e: ev::MouseEvent| {
+        e.prevent_default();
+        state
+            .get_untracked()
+            .iterate
+            .get_untracked()
+            .milestones
+            .update(|m| m.push(Milestone::new()));
+    };
+
+    let on_remove = move |id: Uuid| {
+        state
+            .get_untracked()
+            .iterate
+            .get_untracked()
+            .milestones
+            .update(|m| m.retain(|m| m.id != id));
+    };
+
+    view! {
+        <div id="iterate-milestones" class="mb-4">
+            <h4 class="text-xl mb-4 w-full text-center">
+                {t!("worksheets.iterate.label_milestones")}
+            </h4>
+            <ol class="flex flex-col gap-4 mb-4">
+                <For
+                    each=move || milestones.get()
+                    key=|m| m.id
+                    let:milestone
+                >
+                    {
+                        let id = milestone.id;
+                        let title = Signal::derive(move || {
+                            milestones
+                                .get()
+                                .into_iter()
+                                .find(|m| m.id == id)
+                                .map(|m| m.title)
+                                .unwrap_or_default()
+                        });
+                        let date = Signal::derive(move || {
+                            milestones
+                                .get()
+                                .into_iter()
+                                .find(|m| m.id == id)
+                                .and_then(|m| m.date)
+                        });
+                        let date_state = FormState::with_id(id, date.get_untracked());
+                        create_effect(move |_| {
+                            let value = date.get();
+                            if date_state.get_untracked() != value {
+                                date_state.set(value);
+                            }
+                        });
+                        create_effect(move |_| {
+                            let value = date_state.get();
+                            if date.get_untracked() != value {
+                                update(id, &|m| m.date = value);
+                            }
+                        });
+                        let invalid = Signal::derive(move || {
+                            let iterate = iterate.get();
+                            iterate
+                                .milestones
+                                .iter()
+                                .find(|m| m.id == id)
+                                .map(|m| !iterate.is_valid_milestone(m))
+                                .unwrap_or(false)
+                        });
+                        view! {
+                            <li class="grid grid-cols-[1fr_auto_auto] gap-2 items-center">
+                                <input
+                                    type="text"
+                                    class=INPUT_CLASS
+                                    aria-label=t!("worksheets.iterate.label_milestone").to_string()
+                                    placeholder=t!("worksheets.iterate.placeholder_milestone").to_string()
+                                    prop:value=title
+                                    on:input=move |ev| {
+                                        let value = event_target_value(&ev);
+                                        update(id, &|m| m.title = value.clone());
+                                    }
+                                />
+                                <DateInputView
+                                    value=Signal::derive(move || date_state)
+                                    min=Signal::derive(move || iterate.get().start_date)
+                                    max=Signal::derive(move || iterate.get().end_date)
+                                    attr:aria-label=t!("worksheets.iterate.label_milestone_date").to_string()
+                                    attr:aria-invalid=move || invalid.get().to_string()
+                                />
+                                <ButtonView
+                                    cta=-1
+                                    attr:title=t!("worksheets.iterate.remove_milestone").to_string()
+                                    attr:aria-label=t!("worksheets.iterate.remove_milestone").to_string()
+                                    on:click=move |e| {
+                                        e.prevent_default();
+                                        on_remove(id);
+                                    }
+                                >
+                                    <IconView icon="Delete"/>
+                                </ButtonView>
+                            </li>
+                        }
+                    }
+                </For>
+            </ol>
+            <div class="flex justify-center">
+                <ButtonView on:click=on_add>
+                    {t!("worksheets.iterate.add_milestone")}
+                </ButtonView>
+            </div>
+        </div>
+    }
+}
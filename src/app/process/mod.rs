@@ -1,23 +1,63 @@
 mod about;
+mod affinity_map;
+mod collab;
 mod compromise;
+mod custom_step;
+mod feedback;
+mod follow_up;
 mod implement;
+mod inferrence_stream;
 mod inquire;
 mod iterate;
+mod milestones;
 mod problem;
+mod prompt_budget;
+mod prompt_guard;
+mod prompt_templates;
+mod reflect;
 mod send_inquery;
+mod share;
 mod solution;
+mod solution_matrix;
+mod stakeholder_map;
 mod stepper;
+mod suggest;
+mod suggest_edits;
 mod switch;
+mod validation;
+mod wk_calendar;
+mod wk_document;
 mod wk_download;
+mod wk_export;
 
 pub use about::*;
+pub use affinity_map::*;
+pub use collab::*;
 pub use compromise::*;
+pub use custom_step::*;
+pub use feedback::*;
+pub use follow_up::*;
 pub use implement::*;
+pub use inferrence_stream::*;
 pub use inquire::*;
 pub use iterate::*;
+pub use milestones::*;
 pub use problem::*;
+pub use prompt_budget::*;
+pub use prompt_guard::*;
+pub use prompt_templates::*;
+pub use reflect::*;
 pub use send_inquery::*;
+pub use share::*;
 pub use solution::*;
+pub use solution_matrix::*;
+pub use stakeholder_map::*;
 pub use stepper::*;
+pub use suggest::*;
+pub use suggest_edits::*;
 pub use switch::*;
+pub use validation::*;
+pub use wk_calendar::*;
+pub use wk_document::*;
 pub use wk_download::*;
+pub use wk_export::*;
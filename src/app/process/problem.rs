@@ -4,15 +4,20 @@ use leptos_router::*;
 use uuid::Uuid;
 use web_time::Instant;
 
-use form_signal::FormState;
-
 use crate::app::{
     components::{
-        use_example_ctx, use_wk_ctx, use_wk_state, ButtonSize, ButtonView, DescriptionView,
-        DragListCtx, HistoryEntry, ListInputView, ReadOnlyListView, ReadOnlyView, StringInputView,
-        UndoRemove, WorksheetHeader,
+        use_example_ctx, use_example_shared, use_wk_ctx, use_wk_state, ButtonSize, ButtonView,
+        DescriptionView, DragListCtx, HistoryEntry, ListInputView, ModalView, ReadOnlyListView,
+        ReadOnlyView, StringInputView, UndoRemove, WorksheetHeader,
+    },
+    next_step_link,
+    process::{
+        AffinityMapView, StakeholderMapView, SuggestTarget, SuggestView, ThemesScaffoldView,
+    },
+    state::{
+        keyed_values, ChoiceList, Completenes, KeyedEntry, ProblemWKFormState, ProcessStep,
+        RemovedEntry, Stakeholder,
     },
-    state::{Completenes, ProcessStep},
     tabs_signal, use_lang,
 };
 
@@ -21,8 +26,7 @@ use crate::app::{
 pub fn ProblemView() -> impl IntoView {
     let wk_state = use_wk_state();
     let wk_ctx = use_wk_ctx();
-    let lang = use_lang();
-    let link = Signal::derive(move || format!("/{}/process/2", lang.get()));
+    let link = next_step_link(ProcessStep::Problem);
 
     let problem_statement = Signal::derive(move || {
         wk_state
@@ -44,7 +48,7 @@ pub fn ProblemView() -> impl IntoView {
             .unwrap_or_default()
     });
     let problems_value_add = move |(next, index): (String, Option<usize>)| {
-        let next = FormState::new(next);
+        let next = ProblemWKFormState::problems_entry(Uuid::new_v4(), next);
         let id = next.id;
         wk_state.get().problem.update(move |p| {
             p.problems.insert(index.unwrap_or(p.problems.len()), next);
@@ -52,21 +56,36 @@ pub fn ProblemView() -> impl IntoView {
         id
     };
     let problems_value_remove = move |id: Uuid| {
-        wk_state.get().problem.update(move |p| {
+        let problem = wk_state.get().problem;
+        let cluster = problem.get_untracked().cluster_of(id);
+        problem
+            .get_untracked()
+            .move_to_cluster(id, None, Uuid::nil());
+        problem.update(move |p| {
             let i = p.problems.iter().position(|v| v.id == id).unwrap();
-            let removed = p.problems.remove(i).get_untracked();
+            let removed = p.problems.remove(i);
+            let removed = RemovedEntry {
+                id,
+                value: removed.get_untracked(),
+                chosen: false,
+                attached: cluster,
+            };
             problem_delete_history.update(|h| h.push((removed, i, Instant::now())));
         })
     };
-    let problem_restore = move |(val, at, _): HistoryEntry<String>| {
-        wk_state.get().problem.update(move |p| {
-            if p.problems.len() >= at {
-                p.problems.insert(at, FormState::new(val));
-            } else {
-                p.problems.push(FormState::new(val));
+    let problem_restore =
+        move |(removed, at, _): HistoryEntry<RemovedEntry<String, Option<(Uuid, Uuid)>>>| {
+            let problem = wk_state.get().problem;
+            if let Some((cluster, after)) = removed.attached {
+                problem
+                    .get_untracked()
+                    .move_to_cluster(removed.id, Some(cluster), after);
             }
-        })
-    };
+            problem.update(move |p| {
+                let entry = ProblemWKFormState::problems_entry(removed.id, removed.value);
+                p.problems.insert(at.min(p.problems.len()), entry);
+            })
+        };
 
     let stakeholders_data = Signal::derive(move || {
         wk_state
@@ -77,7 +96,7 @@ pub fn ProblemView() -> impl IntoView {
             .unwrap_or_default()
     });
     let stakeholders_value_add = move |(next, index): (String, Option<usize>)| {
-        let next = FormState::new(next);
+        let next = ProblemWKFormState::stakeholders_entry(Uuid::new_v4(), next.into());
         let id = next.id;
         wk_state.get().problem.update(move |p| {
             p.stakeholders
@@ -85,22 +104,86 @@ pub fn ProblemView() -> impl IntoView {
         });
         id
     };
-    let stakeholders_value_remove = move |id: Uuid| {
-        wk_state.get().problem.update(move |p| {
+    // another entry of the same stakeholder
+    let same_stakeholder = move |id: Uuid| {
+        let problem = wk_state.get().problem.get_untracked();
+        problem
+            .stakeholders
+            .iter()
+            .find(|s| s.id == id)
+            .map(|s| s.get_untracked().name)
+            .filter(|name| !name.is_empty())
+            .and_then(|name| {
+                problem
+                    .stakeholders
+                    .iter()
+                    .find(|s| s.id != id && s.get_untracked().name == name)
+                    .map(|s| s.id)
+            })
+    };
+    let remove_stakeholder = move |id: Uuid| {
+        let same = same_stakeholder(id);
+        let problem = wk_state.get().problem;
+        if let Some(same) = same {
+            problem.get_untracked().move_profile(id, same);
+        }
+        let solutions = wk_state.get().solutions.get_untracked();
+        let ratings = solutions.stakeholder_ratings(id);
+        solutions.move_stakeholder_ratings(id, same);
+        let compromise = wk_state.get().compromise.get_untracked();
+        let chosen = compromise.is_chosen(ChoiceList::Stakeholders, id);
+        compromise.forget_choice(ChoiceList::Stakeholders, id);
+        problem.update(move |p| {
             let i = p.stakeholders.iter().position(|v| v.id == id).unwrap();
-            let removed = p.stakeholders.remove(i).get_untracked();
+            let removed = p.stakeholders.remove(i);
+            let removed = RemovedEntry {
+                id,
+                value: removed.get_untracked(),
+                chosen,
+                attached: ratings,
+            };
             stakeholder_delete_history.update(|h| h.push((removed, i, Instant::now())));
         })
     };
-    let stakeholder_restore = move |(val, at, _): HistoryEntry<String>| {
-        wk_state.get().problem.update(move |p| {
-            if p.stakeholders.len() >= at {
-                p.stakeholders.insert(at, FormState::new(val));
-            } else {
-                p.stakeholders.push(FormState::new(val));
+    let (pending_removal, set_pending_removal) = create_signal(None::<Uuid>);
+    let stakeholders_value_remove = move |id: Uuid| {
+        let compromise = wk_state.get().compromise.get_untracked();
+        if compromise.is_chosen(ChoiceList::Stakeholders, id) {
+            // another entry of the same stakeholder keeps the choice
+            match same_stakeholder(id) {
+                Some(other) => compromise.replace_choice(ChoiceList::Stakeholders, id, other),
+                None => {
+                    set_pending_removal.set(Some(id));
+                    return;
+                }
             }
-        })
+        }
+        remove_stakeholder(id);
     };
+    let on_confirm_removal = Callback::new(move |confirmed: bool| {
+        if let Some(id) = pending_removal.get_untracked().filter(|_| confirmed) {
+            remove_stakeholder(id);
+        }
+        set_pending_removal.set(None);
+    });
+    let stakeholder_restore =
+        move |(removed, at, _): HistoryEntry<RemovedEntry<Stakeholder, Vec<(Uuid, u8)>>>| {
+            let solutions = wk_state.get().solutions.get_untracked();
+            for (solution, rating) in removed.attached {
+                solutions.update_rating(solution, |r| r.set_stakeholder(removed.id, Some(rating)));
+            }
+            if removed.chosen {
+                wk_state
+                    .get()
+                    .compromise
+                    .get_untracked()
+                    .choose(ChoiceList::Stakeholders, removed.id);
+            }
+            wk_state.get().problem.update(move |p| {
+                let entry = ProblemWKFormState::stakeholders_entry(removed.id, removed.value);
+                p.stakeholders.insert(at.min(p.stakeholders.len()), entry);
+            })
+        };
     let stakeholders_autocomplete = Signal::derive(move || {
         wk_state
             .try_get()
@@ -120,33 +203,40 @@ pub fn ProblemView() -> impl IntoView {
         let data = wk_state.get().problem.get().get();
         data.problems
             .iter()
-            .filter(|e| !e.is_empty())
+            .filter(|(_, e)| !e.is_empty())
             .next()
             .is_none()
             || data
                 .stakeholders
                 .iter()
-                .filter(|e| !e.is_empty())
+                .filter(|(_, e)| !e.name.is_empty())
                 .next()
                 .is_none()
     });
 
     let disable_cta = Signal::derive(move || !wk_state.get().problem.get().get().is_complete());
 
+    let (show_affinity, set_show_affinity) = create_signal(false);
+    let has_clusters =
+        Signal::derive(move || !wk_state.get().problem.get().clusters.get().is_empty());
+    let affinity_mode = Signal::derive(move || show_affinity.get() || has_clusters.get());
+
     DragListCtx::provide(Callback::new(
-        move |(entry, list_name, insert_after): (FormState<String>, String, Uuid)| {
+        move |((id, text), list_name, insert_after): (KeyedEntry, String, Uuid)| {
             let wk = wk_state.get().problem;
-            log::debug!(
-                "dropped {} on {list_name} to insert after {insert_after}",
-                entry.id
-            );
+            log::debug!("dropped {id} on {list_name} to insert after {insert_after}");
 
             wk.update(|wk| {
                 match list_name.as_str() {
                     "problems" => {
-                        let old_pos = wk.problems.iter().position(|f| f.id == entry.id);
-                        wk.problems.retain(|f| f.id != entry.id);
-                        wk.stakeholders.retain(|f| f.id != entry.id);
+                        let old_pos = wk.problems.iter().position(|f| f.id == id);
+                        // entries from the other list take the validators of this one
+                        let entry = match old_pos {
+                            Some(pos) => wk.problems[pos].clone(),
+                            None => ProblemWKFormState::problems_entry(id, text),
+                        };
+                        wk.problems.retain(|f| f.id != id);
+                        wk.stakeholders.retain(|f| f.id != id);
                         let new_pos = wk.problems.iter().position(|f| f.id == insert_after);
 
                         if let Some(pos) = new_pos.map(|p| p + 1).or(old_pos) {
@@ -156,9 +246,14 @@ pub fn ProblemView() -> impl IntoView {
                         }
                     }
                     "stakeholders" => {
-                        let old_pos = wk.stakeholders.iter().position(|f| f.id == entry.id);
-                        wk.problems.retain(|f| f.id != entry.id);
-                        wk.stakeholders.retain(|f| f.id != entry.id);
+                        let old_pos = wk.stakeholders.iter().position(|f| f.id == id);
+                        // entries from the other list take the validators of this one
+                        let entry = match old_pos {
+                            Some(pos) => wk.stakeholders[pos].clone(),
+                            None => ProblemWKFormState::stakeholders_entry(id, text.into()),
+                        };
+                        wk.problems.retain(|f| f.id != id);
+                        wk.stakeholders.retain(|f| f.id != id);
                         let new_pos = wk.stakeholders.iter().position(|f| f.id == insert_after);
 
                         if let Some(pos) = new_pos.map(|p| p + 1).or(old_pos) {
@@ -196,7 +291,7 @@ pub fn ProblemView() -> impl IntoView {
                     <p>{t!("worksheets.problem.instruction_1")}</p>
                 </div>
                 <div class="grid lg:grid-cols-2 text-center mb-4 gap-6">
-                    <div>
+                    <div id="problem-problems">
                         <h4 class="text-xl mb-4">
                             {t!("worksheets.problem.label_problems")}
                         </h4>
@@ -210,7 +305,7 @@ pub fn ProblemView() -> impl IntoView {
                             drop_target_name="problems"
                     />
                     </div>
-                    <div>
+                    <div id="problem-stakeholders">
                         <h4 class="text-xl mb-4">
                             {t!("worksheets.problem.label_stakeholders")}
                         </h4>
@@ -224,13 +319,36 @@ pub fn ProblemView() -> impl IntoView {
                             autocomplete=stakeholders_autocomplete
                             drop_target_name="stakeholders"
                         />
+                        <SuggestView
+                            target=SuggestTarget::Stakeholders
+                            on_accept=move |value: String| {
+                                stakeholders_value_add((value, None));
+                            }
+                        />
                     </div>
                 </div>
+                <Show
+                    when=move || affinity_mode.get()
+                    fallback=move || view! {
+                        <div class="flex justify-center mb-4">
+                            <ButtonView on:click=move |e| {
+                                e.prevent_default();
+                                set_show_affinity.set(true);
+                            }>
+                                {t!("worksheets.problem.affinity.start")}
+                            </ButtonView>
+                        </div>
+                    }
+                >
+                    <AffinityMapView/>
+                </Show>
+                <StakeholderMapView/>
                 <hr class="border-t border-slate-400 mt-4 mb-8"/>
-                <label>
+                <label id="problem-problem_statement">
                     <div class="max-w-prose mb-2 whitespace-pre-line">
                         <p>{t!("worksheets.problem.instruction_2")}</p>
                     </div>
+                    <ThemesScaffoldView/>
                     <StringInputView
                         input_type="textarea"
                         disabled={disable_statement}
@@ -258,6 +376,13 @@ pub fn ProblemView() -> impl IntoView {
             history=stakeholder_delete_history
             on_restore=stakeholder_restore
         />
+        <ModalView
+            when=Signal::derive(move || pending_removal.get().is_some())
+            cancel_btn=true
+            on_resolve=on_confirm_removal
+        >
+            <p class="max-w-prose mb-4 whitespace-pre-line">{t!("worksheets.compromise.confirm_remove")}</p>
+        </ModalView>
     }
 }
 
@@ -294,15 +419,20 @@ pub fn FixedProblemStatement() -> impl IntoView {
 pub fn ExampleProblemView() -> impl IntoView {
     let lang = use_lang();
     let (wk, example) = use_example_ctx();
+    let shared = use_example_shared();
     let wk_ctx = use_wk_ctx();
 
-    let tabs = tabs_signal(ProcessStep::Problem);
+    let tabs = if shared {
+        Signal::derive(Vec::new)
+    } else {
+        tabs_signal(ProcessStep::Problem)
+    };
 
     let problem_statement = Signal::derive(move || wk.get().problem.problem_statement);
 
-    let problems_data = Signal::derive(move || wk.get().problem.problems);
+    let problems_data = Signal::derive(move || keyed_values(&wk.get().problem.problems));
 
-    let stakeholders_data = Signal::derive(move || wk.get().problem.stakeholders);
+    let stakeholders_data = Signal::derive(move || keyed_values(&wk.get().problem.stakeholders));
 
     let title = Signal::derive(move || {
         t!(
@@ -335,9 +465,11 @@ pub fn ExampleProblemView() -> impl IntoView {
                 <p class="whitespace-pre-line">
                     {move || example.get().description}
                 </p>
-                <A href=case_href attr:class="underline">
-                    {move || t!("worksheets.view_example", title=example.get().title)}
-                </A>
+                <Show when=move || !shared>
+                    <A href=case_href attr:class="underline">
+                        {move || t!("worksheets.view_example", title=example.get().title)}
+                    </A>
+                </Show>
             </DescriptionView>
             <form role="form">
                 <div class="max-w-prose mb-4 whitespace-pre-line italic">
@@ -0,0 +1,131 @@
+#![cfg_attr(not(feature = "ssr"), allow(unused))]
+
+use serde_json::Value;
+
+/// context window of the model in tokens
+pub const PROMPT_BUDGET: u32 = 4096;
+
+/// lists of the workbook shortened to fit the budget, the first are shortened first
+const TRIM_ORDER: [&str; 11] = [
+    "iterate.milestones",
+    "iterate.external_resources",
+    "iterate.resources",
+    "solutions.ratings",
+    "problem.stakeholder_map",
+    "problem.clusters",
+    "implement.best",
+    "implement.now",
+    "solutions.solutions",
+    "problem.stakeholders",
+    "problem.problems",
+];
+
+/// entries a shortened list keeps at least
+const MIN_ENTRIES: usize = 2;
+
+/// most characters of a text once all lists are shortened
+const SHORT_TEXT_CAP: usize = 160;
+
+/// rough number of tokens of a text, a token is about four ascii characters,
+/// two characters of other alphabets, e.g. cyrillic, or a single ideograph or kana
+pub fn estimate_tokens(text: &str) -> u32 {
+    let quarters = text
+        .chars()
+        .map(|c| match c as u32 {
+            0..=0x7f => 1,
+            0x80..=0x2fff => 2,
+            _ => 4,
+        })
+        .sum::<u32>();
+    quarters.div_ceil(4)
+}
+
+/// json without whitespace between tokens
+pub fn compact_json(json: &str) -> serde_json::Result<String> {
+    serde_json::to_string(&serde_json::from_str::<Value>(json)?)
+}
+
+fn tokens(value: &Value) -> u32 {
+    estimate_tokens(value.to_string().as_str())
+}
+
+/// removes the last entries of a list until the value fits, the removed entries are
+/// summarized by their count, returns whether the list was shortened
+fn shorten_list(value: &mut Value, path: &str, budget: u32) -> bool {
+    let pointer = format!("/{}", path.replace('.', "/"));
+    let mut removed = 0;
+
+    while tokens(value) > budget {
+        let Some(list) = value
+            .pointer_mut(pointer.as_str())
+            .and_then(|v| v.as_array_mut())
+        else {
+            break;
+        };
+        // the summary of the entries removed so far
+        if removed > 0 {
+            list.pop();
+        }
+        let done = list.len() <= MIN_ENTRIES;
+        if !done {
+            list.pop();
+            removed += 1;
+        }
+        if removed > 0 {
+            list.push(Value::String(format!("… {removed} more")));
+        }
+        if done {
+            break;
+        }
+    }
+
+    removed > 0
+}
+
+/// caps every text of the value at `cap` characters
+fn shorten_texts(value: &mut Value, cap: usize) -> bool {
+    match value {
+        Value::String(s) if s.chars().count() > cap => {
+            *s = s.chars().take(cap).collect::<String>();
+            s.push('…');
+            true
+        }
+        Value::Array(items) => {
+            let mut changed = false;
+            for v in items.iter_mut() {
+                changed |= shorten_texts(v, cap);
+            }
+            changed
+        }
+        Value::Object(map) => {
+            let mut changed = false;
+            for v in map.values_mut() {
+                changed |= shorten_texts(v, cap);
+            }
+            changed
+        }
+        _ => false,
+    }
+}
+
+/// compact json of the workbook in at most `budget` tokens if possible,
+/// with the fields which were shortened to fit
+pub fn fit_json(json: &str, budget: u32) -> serde_json::Result<(String, Vec<String>)> {
+    let mut value: Value = serde_json::from_str(json)?;
+    let mut trimmed = vec![];
+
+    for path in TRIM_ORDER {
+        if tokens(&value) <= budget {
+            break;
+        }
+        if shorten_list(&mut value, path, budget) {
+            trimmed.push(path.to_string());
+        }
+    }
+
+    if tokens(&value) > budget && shorten_texts(&mut value, SHORT_TEXT_CAP) {
+        trimmed.push("text".to_string());
+    }
+
+    Ok((value.to_string(), trimmed))
+}
@@ -0,0 +1,210 @@
+#![cfg_attr(not(feature = "ssr"), allow(unused))]
+
+use serde_json::Value;
+
+/// tokens of the prompt format, never passed on from user input or model output
+const CONTROL_TOKENS: [&str; 6] = ["[INST]", "[/INST]", "<<SYS>>", "<</SYS>>", "<s>", "</s>"];
+
+pub const FIELD_CAP: usize = 600;
+
+pub const LIST_CAP: usize = 24;
+
+pub const QUESTION_CAP: usize = 1200;
+
+pub const MESSAGE_CAP: usize = 4000;
+
+/// phrases attempting to change the role or the instructions of the model,
+/// specific enough not to match questions about the worksheets
+const INJECTION_PATTERNS: [&str; 16] = [
+    "ignore previous instructions",
+    "ignore all previous",
+    "ignore the above",
+    "disregard previous",
+    "disregard the above",
+    "forget your instructions",
+    "new instructions:",
+    "from now on you are",
+    "pretend to be a different assistant",
+    "pretend to be an ai",
+    "act as an ai without",
+    "developer mode",
+    "jailbreak",
+    "reveal your system prompt",
+    "repeat your system prompt",
+    "reveal your instructions",
+];
+
+const POLICY_PATTERNS: [(&str, &str); 7] = [
+    ("kill yourself", "harm"),
+    ("self-harm", "harm"),
+    ("suicide", "harm"),
+    ("build a bomb", "harm"),
+    ("http://", "link"),
+    ("https://", "link"),
+    ("www.", "link"),
+];
+
+/// least characters of a response line to count as a leak of the system prompt
+const LEAK_MIN_LEN: usize = 24;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FilteredOutput {
+    pub text: String,
+    pub flags: Vec<String>,
+}
+
+/// removes all occurrences of a token ignoring case and inner whitespace, e.g. `[ inst ]`
+fn remove_token(value: &str, token: &str) -> String {
+    let token = token
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(|c| c.to_lowercase())
+        .collect::<Vec<_>>();
+    let chars = value.chars().collect::<Vec<_>>();
+
+    let mut out = String::with_capacity(value.len());
+    let mut i = 0;
+    'outer: while i < chars.len() {
+        let mut j = i;
+        let mut k = 0;
+        while k < token.len() && j < chars.len() {
+            if chars[j].is_whitespace() && k > 0 {
+                j += 1;
+                continue;
+            }
+            if chars[j].to_lowercase().ne(std::iter::once(token[k])) {
+                break;
+            }
+            j += 1;
+            k += 1;
+        }
+        if k == token.len() {
+            i = j;
+            continue 'outer;
+        }
+        out.push(chars[i]);
+        i += 1;
+    }
+    out
+}
+
+pub fn strip_control_tokens(value: &str) -> String {
+    let mut stripped = value.to_string();
+    // removing a token may join the parts of another one
+    loop {
+        let next = CONTROL_TOKENS
+            .iter()
+            .fold(stripped.clone(), |acc, tok| remove_token(acc.as_str(), tok));
+        if next == stripped {
+            return stripped;
+        }
+        stripped = next;
+    }
+}
+
+pub fn escape_text(value: &str, cap: usize) -> String {
+    let mut escaped = strip_control_tokens(value.replace("```", "'''").as_str());
+
+    if escaped.chars().count() > cap {
+        escaped = escaped.chars().take(cap).collect::<String>();
+        escaped.push('…');
+    }
+    escaped
+}
+
+/// escapes and caps every string and list of a json document, returned without whitespace,
+/// texts with injection phrases are left out, with the fields which were shortened
+pub fn guard_json(json: &str) -> Result<(String, Vec<String>), serde_json::Error> {
+    fn guard(value: &mut Value, path: &str, dropped: &mut Vec<String>) {
+        let mut drop = || {
+            if !dropped.iter().any(|d| d == path) {
+                dropped.push(path.to_string());
+            }
+        };
+        match value {
+            Value::String(s) if is_injection(s) => {
+                s.clear();
+                drop();
+            }
+            Value::String(s) => *s = escape_text(s, FIELD_CAP),
+            Value::Array(items) => {
+                let len = items.len();
+                items.retain(|v| !v.as_str().is_some_and(is_injection));
+                items.truncate(LIST_CAP);
+                if items.len() < len {
+                    drop();
+                }
+                items.iter_mut().for_each(|v| guard(v, path, dropped));
+            }
+            Value::Object(map) => map.iter_mut().for_each(|(key, v)| {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                guard(v, path.as_str(), dropped)
+            }),
+            _ => {}
+        }
+    }
+
+    let mut value: Value = serde_json::from_str(json)?;
+    let mut dropped = vec![];
+    guard(&mut value, "", &mut dropped);
+    Ok((serde_json::to_string(&value)?, dropped))
+}
+
+pub fn injection_signals(value: &str) -> Vec<&'static str> {
+    let value = value
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase();
+    INJECTION_PATTERNS
+        .iter()
+        .filter(|p| value.contains(*p))
+        .copied()
+        .collect()
+}
+
+pub fn is_injection(value: &str) -> bool {
+    !injection_signals(value).is_empty()
+}
+
+fn system_text(prompt: &str) -> Vec<&str> {
+    prompt
+        .split("<<SYS>>")
+        .skip(1)
+        .filter_map(|s| s.split("<</SYS>>").next())
+        // the workbook section is written by the user
+        .filter(|s| !s.contains("```json"))
+        .collect()
+}
+
+pub fn filter_output(prompt: &str, response: &str) -> FilteredOutput {
+    let system = system_text(prompt);
+
+    let lines = response.lines().collect::<Vec<_>>();
+    let kept = lines
+        .iter()
+        .filter(|line| {
+            let line = line.trim();
+            line.chars().count() < LEAK_MIN_LEN || !system.iter().any(|s| s.contains(line))
+        })
+        .copied()
+        .collect::<Vec<_>>();
+    let text = strip_control_tokens(kept.join("\n").as_str());
+
+    let lower = text.to_lowercase();
+    let mut flags = POLICY_PATTERNS
+        .iter()
+        .filter(|(p, _)| lower.contains(p))
+        .map(|(_, flag)| flag.to_string())
+        .collect::<Vec<_>>();
+    flags.dedup();
+    if kept.len() < lines.len() {
+        flags.push("leak".to_string());
+    }
+
+    FilteredOutput { text, flags }
+}
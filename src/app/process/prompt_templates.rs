@@ -0,0 +1,243 @@
+#![cfg_attr(not(feature = "ssr"), allow(unused))]
+
+use std::collections::HashMap;
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use strum::VariantArray;
+
+use crate::app::{
+    components::Language,
+    state::{ChatMessage, InqueryOption, WorkSheets},
+    use_lang,
+};
+
+use super::{EditField, SuggestTarget};
+
+const PROMPTS: [(&str, &str); 4] = [
+    ("en", include_str!("../../../prompts/en.json")),
+    ("ru", include_str!("../../../prompts/ru.json")),
+    ("nl", include_str!("../../../prompts/nl.json")),
+    ("ja", include_str!("../../../prompts/ja.json")),
+];
+
+/// directory the prompts are mounted to, edits apply without recompiling
+const PROMPTS_DIR: &str = "/prompts";
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptTemplate {
+    /// bumped on changes of the wording, responses cached for earlier versions are not served
+    #[serde(default)]
+    pub version: u32,
+    pub system: Vec<String>,
+    /// the workbook section, `{empty}` and `{workbook}` are replaced with json
+    pub workbook: Vec<String>,
+    pub response_language: String,
+    /// by [InqueryOption], `{custom_prompt}` is replaced with the prompt of the user
+    pub options: HashMap<String, PromptOption>,
+    /// instruction to answer with edits of the workbook as json,
+    /// `{fields}` is replaced with the fields which can be edited
+    #[serde(default)]
+    pub edits: String,
+    #[serde(default)]
+    pub list_format: String,
+    #[serde(default)]
+    pub suggestions: HashMap<String, String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptOption {
+    pub instruction: String,
+    #[serde(default)]
+    pub context: Option<String>,
+}
+
+impl PromptTemplate {
+    /// the english template with the overrides of the language
+    pub fn load(lang: &Language) -> anyhow::Result<PromptTemplate> {
+        let mut template = serde_json::from_str(Self::source(PROMPTS[0].0).as_str())?;
+        if let Some((lang, _)) = PROMPTS[1..].iter().find(|(l, _)| *l == lang.to_string()) {
+            let overrides = serde_json::from_str(Self::source(lang).as_str())?;
+            merge(&mut template, overrides);
+        }
+
+        Ok(serde_json::from_value(template)?)
+    }
+
+    fn source(lang: &str) -> String {
+        std::fs::read_to_string(format!("{PROMPTS_DIR}/{lang}.json"))
+            .ok()
+            .or_else(|| {
+                PROMPTS
+                    .iter()
+                    .find(|(l, _)| *l == lang)
+                    .map(|(_, p)| p.to_string())
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn render(
+        &self,
+        option: InqueryOption,
+        empty: &str,
+        workbook: &str,
+        custom_prompt: &str,
+    ) -> String {
+        let option = self
+            .options
+            .get(option.to_string().as_str())
+            .cloned()
+            .unwrap_or_default();
+
+        self.frame(
+            empty,
+            workbook,
+            option.context,
+            option
+                .instruction
+                .replace("{custom_prompt}", custom_prompt)
+                .as_str(),
+        )
+    }
+
+    pub fn render_suggestions(&self, target: SuggestTarget, empty: &str, workbook: &str) -> String {
+        let instruction = self
+            .suggestions
+            .get(target.to_string().as_str())
+            .cloned()
+            .unwrap_or_default();
+
+        self.frame(
+            empty,
+            workbook,
+            None,
+            format!("{instruction}\n{}", self.list_format).as_str(),
+        )
+    }
+
+    pub fn render_edits(&self, empty: &str, workbook: &str) -> String {
+        let fields = EditField::VARIANTS
+            .iter()
+            .map(|f| format!("\"{f}\""))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        self.frame(
+            empty,
+            workbook,
+            None,
+            self.edits.replace("{fields}", fields.as_str()).as_str(),
+        )
+    }
+
+    /// continues the prompt of an inquery with the previous exchange and a question,
+    /// the first message of the exchange answers the inquery
+    pub fn render_follow_up(
+        &self,
+        inquery: &str,
+        exchange: &[ChatMessage],
+        question: &str,
+    ) -> String {
+        let mut prompt = inquery.to_string();
+        for message in exchange {
+            if message.is_user() {
+                prompt.push_str(format!("[INST]\n{}\n[/INST]\n", message.text).as_str());
+            } else {
+                prompt.push_str(format!("{}\n", message.text.trim()).as_str());
+            }
+        }
+        prompt.push_str(
+            format!("[INST]\n{question}\n{}\n[/INST]\n", self.response_language).as_str(),
+        );
+        prompt
+    }
+
+    fn frame(
+        &self,
+        empty: &str,
+        workbook: &str,
+        context: Option<String>,
+        instruction: &str,
+    ) -> String {
+        let workbook = self
+            .workbook
+            .join("\n")
+            .replace("{empty}", empty)
+            .replace("{workbook}", workbook);
+
+        let context = context
+            .map(|c| format!("<<SYS>>\n{c}\n<</SYS>>\n"))
+            .unwrap_or_default();
+
+        format!(
+            "<<SYS>>\n{}\n<</SYS>>\n<<SYS>>\n{workbook}\n<</SYS>>\n{context}[INST]\n{instruction}\n{}\n[/INST]\n",
+            self.system.join("\n"),
+            self.response_language,
+        )
+    }
+}
+
+/// replaces the values of `base` with those of `overrides`, objects are merged by key
+fn merge(base: &mut serde_json::Value, overrides: serde_json::Value) {
+    match (base, overrides) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                match base.get_mut(key.as_str()) {
+                    Some(base) => merge(base, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overrides) => *base = overrides,
+    }
+}
+
+/// the prompt sent for the workbook, only available in debug builds
+#[server(PreviewInferrencePrompt, "/api")]
+#[cfg_attr(not(debug_assertions), allow(unreachable_code, unused))]
+pub async fn preview_inferrence_prompt(
+    wk: WorkSheets,
+    lang: Language,
+) -> Result<String, ServerFnError<String>> {
+    #[cfg(not(debug_assertions))]
+    {
+        return Err(ServerFnError::Request("Not found".to_string()));
+    }
+
+    #[cfg(debug_assertions)]
+    {
+        use super::inferrence_prompt;
+
+        inferrence_prompt(wk, lang).map(|(prompt, _, _, _)| prompt)
+    }
+}
+
+#[component]
+pub fn PromptPreviewView(#[prop(into)] wk: Signal<WorkSheets>) -> impl IntoView {
+    let lang = use_lang();
+    let preview_action = create_action(|data: &(WorkSheets, Language)| {
+        let (wk, lang) = data.clone();
+        async move {
+            preview_inferrence_prompt(wk, lang)
+                .await
+                .map_err(ServerFnErrorErr::from)
+        }
+    });
+
+    view! {
+        <details class="mt-8" on:toggle=move |_| {
+            preview_action.dispatch((wk.get_untracked(), lang.get_untracked()));
+        }>
+            <summary class="cursor-pointer">{t!("worksheets.inquire.preview_prompt")}</summary>
+            <pre class="text-xs whitespace-pre-wrap mt-2">
+                {move || match preview_action.value().get() {
+                    Some(Ok(prompt)) => prompt,
+                    Some(Err(e)) => e.to_string(),
+                    None => String::default(),
+                }}
+            </pre>
+        </details>
+    }
+}
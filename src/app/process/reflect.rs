@@ -0,0 +1,240 @@
+use leptos::*;
+use leptos_meta::*;
+use leptos_router::use_navigate;
+use strum::VariantArray;
+use uuid::Uuid;
+use web_time::Instant;
+
+use crate::app::{
+    components::{
+        use_wk_ctx, use_wk_state, ButtonSize, ButtonView, CheckedOption, DescriptionView,
+        HistoryEntry, IconView, ListInputView, ModalView, RadioInputView, StringInputView,
+        UndoRemove, WorksheetHeader,
+    },
+    process::{FixedProblemStatement, ValidationHintView},
+    state::{
+        format_date, use_store, Completenes, ReflectWKFormState, StatementReview,
+        WorkSheetsFormState,
+    },
+    use_lang,
+};
+
+/// step 7, retrospective of the iteration
+#[component]
+pub fn ReflectView() -> impl IntoView {
+    let wk_ctx = use_wk_ctx();
+    let state = use_wk_state();
+    let store = use_store();
+    let lang = use_lang();
+
+    let outcomes = Signal::derive(move || state.get().reflect.get().outcomes);
+    let learnings = Signal::derive(move || state.get().reflect.get().learnings);
+    let statement_review = Signal::derive(move || state.get().reflect.get().statement_review);
+    let revised_statement = Signal::derive(move || state.get().reflect.get().revised_statement);
+
+    let show_revised_statement =
+        Signal::derive(move || statement_review.get().get() == StatementReview::Revise.to_string());
+
+    let outcomes_delete_history = create_rw_signal(vec![]);
+    let learnings_delete_history = create_rw_signal(vec![]);
+
+    let outcomes_value_add = move |(next, index): (String, Option<usize>)| {
+        let next = ReflectWKFormState::outcomes_entry(next);
+        let id = next.id;
+        state.get().reflect.update(move |p| {
+            p.outcomes.insert(index.unwrap_or(p.outcomes.len()), next);
+        });
+        id
+    };
+    let learnings_value_add = move |(next, index): (String, Option<usize>)| {
+        let next = ReflectWKFormState::learnings_entry(next);
+        let id = next.id;
+        state.get().reflect.update(move |p| {
+            p.learnings.insert(index.unwrap_or(p.learnings.len()), next);
+        });
+        id
+    };
+    let outcomes_value_remove = move |id: Uuid| {
+        state.get().reflect.update(move |p| {
+            let i = p.outcomes.iter().position(|v| v.id == id).unwrap();
+            let removed = p.outcomes.remove(i).get_untracked();
+            outcomes_delete_history.update(|h| h.push((removed, i, Instant::now())));
+        })
+    };
+    let learnings_value_remove = move |id: Uuid| {
+        state.get().reflect.update(move |p| {
+            let i = p.learnings.iter().position(|v| v.id == id).unwrap();
+            let removed = p.learnings.remove(i).get_untracked();
+            learnings_delete_history.update(|h| h.push((removed, i, Instant::now())));
+        })
+    };
+    let outcomes_restore = move |(val, at, _): HistoryEntry<String>| {
+        state.get().reflect.update(move |p| {
+            let entry = ReflectWKFormState::outcomes_entry(val);
+            p.outcomes.insert(at.min(p.outcomes.len()), entry);
+        })
+    };
+    let learnings_restore = move |(val, at, _): HistoryEntry<String>| {
+        state.get().reflect.update(move |p| {
+            let entry = ReflectWKFormState::learnings_entry(val);
+            p.learnings.insert(at.min(p.learnings.len()), entry);
+        })
+    };
+
+    let review_options = Signal::derive(|| {
+        StatementReview::VARIANTS
+            .iter()
+            .map(|opt| {
+                let label_name = format!("worksheets.reflect.review_{opt}");
+                CheckedOption {
+                    value: opt.to_string(),
+                    label: view! {
+                        <p class="max-w-prose whitespace-pre-line">
+                            {t!(label_name.as_str()).to_string()}
+                        </p>
+                    }
+                    .into_view(),
+                }
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let report = Signal::derive(move || state.get().reflect.get().get().validate());
+    let disable_next = Signal::derive(move || !report.get().is_valid());
+
+    let (show_confirm, set_show_confirm) = create_signal(false);
+
+    let navigate = use_navigate();
+    let on_confirm = Callback::new(move |confirmed: bool| {
+        set_show_confirm.set(false);
+        if !confirmed {
+            return;
+        }
+        let wk = state.get_untracked().get_untracked().next_iteration();
+        store.update(|s| s.wk = WorkSheetsFormState::new(wk));
+        let link = format!("/{}/process/1", lang.get_untracked());
+        navigate(link.as_str(), Default::default());
+    });
+
+    let iterations = Signal::derive(move || state.get().iterations.get());
+
+    view! {
+        <Title text={move || format!("{} | {} | {}", t!("worksheets.reflect.title"), t!("process.title"), t!("name"))}/>
+        <WorksheetHeader
+            title={t!("worksheets.reflect.title").to_string()}
+            description_id="reflect"
+        />
+        <div class="grow w-full">
+            <DescriptionView
+                hidden=wk_ctx.description_hidden
+                toggle_hidden=wk_ctx.toggle_description_hidden
+            >
+                <p class="whitespace-pre-line">
+                    {t!("worksheets.reflect.description")}
+                </p>
+            </DescriptionView>
+            <form>
+                <FixedProblemStatement/>
+                <div class="max-w-prose mb-4 mt-6 whitespace-pre-line">
+                    <p>{t!("worksheets.reflect.instruction_results")}</p>
+                </div>
+                <div class="grid lg:grid-cols-2 gap-6 mb-4">
+                    <div id="reflect-outcomes">
+                        <h4 class="text-xl mb-4 w-full text-center">
+                            {t!("worksheets.reflect.label_outcomes")}
+                        </h4>
+                        <ListInputView
+                            input_type="text"
+                            data=outcomes
+                            add_value=outcomes_value_add
+                            remove_value=outcomes_value_remove
+                            add_entry_text={t!("worksheets.reflect.add_outcome").to_string()}
+                            placeholder={t!("worksheets.reflect.placeholder_outcome").to_string()}
+                        />
+                    </div>
+                    <div id="reflect-learnings">
+                        <h4 class="text-xl mb-4 w-full text-center">
+                            {t!("worksheets.reflect.label_learnings")}
+                        </h4>
+                        <ListInputView
+                            input_type="text"
+                            data=learnings
+                            add_value=learnings_value_add
+                            remove_value=learnings_value_remove
+                            add_entry_text={t!("worksheets.reflect.add_learning").to_string()}
+                            placeholder={t!("worksheets.reflect.placeholder_learning").to_string()}
+                        />
+                    </div>
+                </div>
+                <div class="max-w-prose mb-4 whitespace-pre-line" id="reflect-statement_review">
+                    <p>{t!("worksheets.reflect.instruction_review")}</p>
+                </div>
+                <RadioInputView options=review_options value=statement_review />
+                <Show when=move || show_revised_statement.get()>
+                    <label class="block mb-4 mt-2" id="reflect-revised_statement">
+                        <p class="mb-2">{t!("worksheets.reflect.label_revised_statement")}</p>
+                        <StringInputView
+                            input_type="textarea"
+                            value=revised_statement
+                            placeholder={t!("worksheets.reflect.placeholder_revised_statement").to_string()}
+                        />
+                    </label>
+                </Show>
+            </form>
+            <div class="flex w-full mt-8 justify-center">
+                <ButtonView
+                    cta=2
+                    size=ButtonSize::Lg
+                    on:click=move |_| set_show_confirm.set(true)
+                    disabled={disable_next}
+                >
+                    <IconView icon="Restart"/>
+                    {t!("worksheets.reflect.cta")}
+                </ButtonView>
+            </div>
+            <ValidationHintView report/>
+            <Show when=move || !iterations.get().is_empty()>
+                <div class="mt-8" id="reflect-iterations">
+                    <h4 class="text-xl mb-4 w-full text-center">
+                        {t!("worksheets.reflect.label_iterations")}
+                    </h4>
+                    <ol class="list-decimal list-inside max-w-prose mx-auto">
+                        {move || iterations
+                            .get()
+                            .into_iter()
+                            .map(|r| {
+                                let dates = format!(
+                                    "{} – {}",
+                                    format_date(r.iterate.start_date),
+                                    format_date(r.iterate.end_date)
+                                );
+                                view! {
+                                    <li class="mb-2">
+                                        {r.iterate.title}
+                                        " "
+                                        <small class="opacity-75">{dates}</small>
+                                    </li>
+                                }
+                            })
+                            .collect_view()}
+                    </ol>
+                </div>
+            </Show>
+        </div>
+        <ModalView
+            when=show_confirm
+            cancel_btn=true
+            on_resolve=on_confirm
+        >
+            <p class="max-w-prose mb-4 whitespace-pre-line">{t!("worksheets.reflect.confirm_next")}</p>
+        </ModalView>
+        <UndoRemove
+            history=outcomes_delete_history
+            on_restore=outcomes_restore
+        />
+        <UndoRemove
+            history=learnings_delete_history
+            on_restore=learnings_restore
+        />
+    }
+}
@@ -3,43 +3,171 @@
 use std::str::FromStr;
 
 use leptos::*;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::app::state::{Contact, InqueryOption, WorkSheets};
+use crate::app::{
+    components::Language,
+    state::{ChatMessage, Contact, InqueryOption, WorkSheets},
+};
 
-fn sanitize_input(value: String) -> String {
-    const CLEAR_OUT: &[&str] = &["[INST]", "[/INST]", "<<SYS>>", "<</SYS>>"];
+use super::PromptTemplate;
 
-    let mut sanitized = value;
-    for tok in CLEAR_OUT {
-        sanitized = sanitized.replace(tok, "")
+#[cfg(feature = "ssr")]
+use super::{
+    compact_json, escape_text, estimate_tokens, filter_output, fit_json, guard_json,
+    injection_signals, MESSAGE_CAP, PROMPT_BUDGET, QUESTION_CAP,
+};
+
+#[cfg(feature = "ssr")]
+use crate::server::safe_error;
+#[cfg(feature = "ssr")]
+use spin_sdk::sqlite::{Connection, Value};
+
+// every chunk submits the prompt with the response so far again, so the prompt is
+// evaluated once per chunk, larger chunks are fewer evaluations but slower updates
+const CHUNK_TOKENS: u32 = 256;
+
+const FOLLOW_UP_TOKENS: u32 = 512;
+
+const CACHE_TTL_DAYS: i64 = 7;
+
+// prompts hold the worksheets of the user, only their ratings are kept longer
+const INFERRENCE_TTL_DAYS: i64 = 30;
+
+/// prefix of the error of a refused question, followed by the injection phrase
+pub const REJECTED: &str = "Rejected: ";
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InferrenceChunk {
+    pub text: String,
+    pub done: bool,
+    #[serde(default)]
+    pub flags: Vec<String>,
+    /// fields of the workbook shortened to fit the prompt
+    #[serde(default)]
+    pub trimmed: Vec<String>,
+}
+
+#[cfg(feature = "ssr")]
+fn guard_question(value: &str) -> Result<String, ServerFnError<String>> {
+    if let Some(phrase) = injection_signals(value).first() {
+        return Err(ServerFnError::Request(format!("{REJECTED}{phrase}")));
     }
-    sanitized
+    Ok(escape_text(value, QUESTION_CAP))
 }
 
-#[cfg_attr(debug_assertions, allow(unreachable_code, unused))]
-#[server(InquireInferrence, "/api")]
-pub async fn inquire_inferrence(
-    wk: WorkSheets,
-    tracking_id: Option<Uuid>,
-) -> Result<String, ServerFnError<String>> {
-    println!("inquire inferrence");
+#[cfg(feature = "ssr")]
+struct InqueryOrigin {
+    option: String,
+    template_version: u32,
+    /// key of the cached response, a stable hash of the sanitized workbook,
+    /// the option and the version of the prompt template
+    cache_key: Uuid,
+    trimmed: Vec<String>,
+}
 
-    #[cfg(debug_assertions)]
-    {
-        return Ok("Helpful answer".to_string());
-    }
+#[cfg(feature = "ssr")]
+fn inquery_origin(
+    wk: &WorkSheets,
+    lang: &Language,
+) -> Result<InqueryOrigin, ServerFnError<String>> {
+    let inquire = wk.inquire.clone();
+    // the whole workbook, the budget only depends on the option and the template
+    let (_, workbook, _) = workbook_json(wk.clone(), u32::MAX)?;
+    let template = PromptTemplate::load(lang).map_err(safe_error)?;
+
+    // the custom prompt is only part of the custom option
+    let custom_prompt = if inquire.inquery_option == InqueryOption::Custom.to_string() {
+        escape_text(inquire.custom_prompt.as_str(), QUESTION_CAP)
+    } else {
+        String::default()
+    };
 
-    use crate::{app::tracking::complete_inferrence, server::safe_error};
-    use spin_sdk::llm;
+    let key = format!(
+        "{lang}\n{}\n{}\n{custom_prompt}\n{workbook}",
+        template.version, inquire.inquery_option,
+    );
+
+    Ok(InqueryOrigin {
+        option: inquire.inquery_option,
+        template_version: template.version,
+        cache_key: Uuid::new_v5(&Uuid::NAMESPACE_OID, key.as_bytes()),
+        trimmed: vec![],
+    })
+}
+
+#[cfg(feature = "ssr")]
+fn cached_inferrence(
+    conn: &Connection,
+    key: Uuid,
+) -> Result<Option<(String, i64)>, ServerFnError<String>> {
+    let sql = r#"
+        SELECT response, generated_tokens
+        FROM inferrence_cache
+        WHERE key = ? AND expires_date > unixepoch();
+"#;
+    let data = conn
+        .execute(sql, &[Value::Blob(Vec::from(key.as_bytes()))])
+        .map_err(safe_error)?;
+
+    let cached = data.rows().next().map(|row| {
+        (
+            row.get::<&str>("response").unwrap_or_default().to_string(),
+            row.get::<i64>("generated_tokens").unwrap_or_default(),
+        )
+    });
+
+    Ok(cached)
+}
+
+#[cfg(feature = "ssr")]
+fn cache_inferrence(
+    conn: &Connection,
+    key: Uuid,
+    response: String,
+    generated_tokens: u32,
+) -> Result<(), ServerFnError<String>> {
+    let sql = r#"
+        DELETE FROM inferrence_cache
+        WHERE expires_date <= unixepoch();
+"#;
+    _ = conn.execute(sql, &[]).map_err(safe_error)?;
 
+    let sql = r#"
+        INSERT OR REPLACE INTO inferrence_cache
+        (key, response, generated_tokens, created_date, expires_date)
+        VALUES (?, ?, ?, unixepoch(), unixepoch() + ? * 86400)
+"#;
+    _ = conn
+        .execute(
+            sql,
+            &[
+                Value::Blob(Vec::from(key.as_bytes())),
+                Value::Text(response),
+                Value::Integer(generated_tokens as i64),
+                Value::Integer(CACHE_TTL_DAYS),
+            ],
+        )
+        .map_err(safe_error)?;
+
+    Ok(())
+}
+
+/// the empty workbook and the worksheets of the process as compact json,
+/// lists are shortened to fit the worksheets into `budget` tokens, see [fit_json]
+#[cfg(feature = "ssr")]
+pub(super) fn workbook_json(
+    wk: WorkSheets,
+    budget: u32,
+) -> Result<(String, String, Vec<String>), ServerFnError<String>> {
     let WorkSheets {
         problem,
         solutions,
         compromise,
         implement,
         iterate,
-        inquire,
+        ..
     } = wk;
 
     let wk = WorkSheets {
@@ -51,103 +179,394 @@ pub async fn inquire_inferrence(
         ..Default::default()
     };
 
-    let workbook = sanitize_input(serde_json::to_string_pretty(&wk).map_err(safe_error)?);
-
-    let empty = serde_json::to_string_pretty(&WorkSheets::default()).map_err(safe_error)?;
-
-    let (inst, i_ctx, max_tokens, temperature) = match InqueryOption::from_str(inquire.inquery_option.as_str()).map_err(safe_error)? {
-        InqueryOption::FirstTime => (
-            "It is a first time entry. How to improve it?".to_string(),
-            Some("Some common mistakes are: choosing a problem which is too intrinsic, forgeting some important stakeholders, confusing stakeholders with shareholders, defining a solution too technically or too vaguely, not having outlined the research, forgetting some necessary resources, showing signs of change avoidance."),
-            1024,
-            0.85
-        ),
-        InqueryOption::ScopeAndTime => (
-            "How to adjust the scope and timeframe of this iteration?".to_string(),
-            Some("It is common to wish for too much at once, underestimate time for implementation and testing a solution."),
-            512,
-            0.65
-        ),
-        InqueryOption::EthicalDesign => (
-            "Suggest an ethical approach to implementing and testing the proposed solution.".to_string(),
-            Some("Ethical design would be concerned with feelings and future wellbeing of enlisted or other potential stakeholders."),
-            512,
-            0.5
-        ),
-        InqueryOption::Narrative => (
-            "Suggest a narrative communicating the main idea of this iteration to a broader audience.".to_string(),
-            Some("A helpfull narrative would illustrate a usecase of the final solution, use a presona, relatable wording."),
-            2056,
-            0.9
-        ),
-        InqueryOption::Custom => (
-            sanitize_input(inquire.custom_prompt),
-            None,
-            2056,
-            0.6
-        ),
+    let (workbook, dropped) =
+        guard_json(wk.to_plain_json().map_err(safe_error)?.as_str()).map_err(safe_error)?;
+    let (workbook, mut trimmed) = fit_json(workbook.as_str(), budget).map_err(safe_error)?;
+    // lists capped or without injection attempts are reported like the ones shortened to fit
+    for field in dropped {
+        if !trimmed.contains(&field) {
+            trimmed.push(field);
+        }
+    }
+
+    let empty = compact_json(
+        WorkSheets::default()
+            .to_plain_json()
+            .map_err(safe_error)?
+            .as_str(),
+    )
+    .map_err(safe_error)?;
+
+    Ok((empty, workbook, trimmed))
+}
+
+/// prompt rendered with the workbook fitted into the context window
+/// next to the rest of the prompt and `max_tokens` of the response,
+/// with the fields which were shortened to fit
+#[cfg(feature = "ssr")]
+pub(super) fn budget_prompt(
+    wk: WorkSheets,
+    max_tokens: u32,
+    render: impl Fn(&str, &str) -> String,
+) -> Result<(String, Vec<String>), ServerFnError<String>> {
+    let (empty, _, _) = workbook_json(WorkSheets::default(), u32::MAX)?;
+    let budget = PROMPT_BUDGET
+        .saturating_sub(max_tokens)
+        .saturating_sub(estimate_tokens(render(empty.as_str(), "").as_str()));
+
+    let (empty, workbook, trimmed) = workbook_json(wk, budget)?;
+
+    Ok((render(empty.as_str(), workbook.as_str()), trimmed))
+}
+
+#[cfg(feature = "ssr")]
+pub fn inferrence_prompt(
+    wk: WorkSheets,
+    lang: Language,
+) -> Result<(String, u32, f32, Vec<String>), ServerFnError<String>> {
+    let inquire = wk.inquire.clone();
+
+    let option = InqueryOption::from_str(inquire.inquery_option.as_str()).map_err(safe_error)?;
+
+    let (max_tokens, temperature) = match option {
+        InqueryOption::FirstTime => (1024, 0.85),
+        InqueryOption::ScopeAndTime => (512, 0.65),
+        InqueryOption::EthicalDesign => (512, 0.5),
+        InqueryOption::Narrative => (2056, 0.9),
+        InqueryOption::Custom => (2056, 0.6),
+    };
+
+    let template = PromptTemplate::load(&lang).map_err(safe_error)?;
+    let custom_prompt = if option == InqueryOption::Custom {
+        guard_question(inquire.custom_prompt.as_str())?
+    } else {
+        String::default()
     };
+    let (prompt, trimmed) = budget_prompt(wk, max_tokens, |empty, workbook| {
+        template.render(option, empty, workbook, custom_prompt.as_str())
+    })?;
 
-    let prompt = format!(
-        r#"
-<<SYS>>
-    In the name of harmony and peace on planet Earth, may we communicate with each other in sincere, concise, and helpful ways. May we all live meaningful and fulfilling lives. May we get the most important work done with care and compassion.
-<</SYS>>
-<<SYS>>
-    You are embedded in a workbook which helps the user to go from multiple problems to a well scoped iteration. The process aims to be very ad hoc, learning is the ultimiate goal, it is inspired by double diamond, human centered design, systems thinking.
-<</SYS>>
-<<SYS>>
-    Workbook:
-    - Problem (exploring the problem space and making a problem statement):
-    - Solutions (the pool of solutions to consider):
-    - Compromise (the chosen solution and stakeholder):
-    - Implement (scoping the iteration):
-    - Test & Iterate (required resources and time-frame):
-
-    Following is the empty workbook:
-
-    ```json
-        {empty}
-    ```
-<</SYS>>
-<<SYS>>
-    User has completed all sections of the workbook in as follows:
-
-    ```json
-        {workbook}
-    ```
-<</SYS>>
-[INST]
-    {inst}
-[/INST]
-{}
-    "#,
-        if let Some(i_ctx) = i_ctx {
-            format!("<<SYS>>{i_ctx}<</SYS>>")
-        } else {
-            String::default()
-        }
-    );
+    Ok((prompt, max_tokens, temperature, trimmed))
+}
 
-    let response = llm::infer_with_options(
-        llm::InferencingModel::Llama2Chat,
-        prompt.as_str(),
-        llm::InferencingParams {
+/// continues the response of a prompt by at most `max_tokens`,
+/// returns the generated text, number of generated tokens and whether the response is complete
+#[cfg(feature = "ssr")]
+fn infer_chunk(
+    prompt: &str,
+    response: &str,
+    max_tokens: u32,
+    temperature: f32,
+) -> anyhow::Result<(String, u32, bool)> {
+    use crate::inference::{inference_backend, InferenceParams};
+
+    let output = inference_backend()?.infer(
+        format!("{prompt}{response}").as_str(),
+        InferenceParams {
             max_tokens,
-            repeat_penalty: 1.1,
-            repeat_penalty_last_n_token_count: 64,
             temperature,
-            top_k: 40,
-            top_p: 0.82,
         },
+    )?;
+
+    Ok((
+        output.text,
+        output.generated_tokens,
+        output.generated_tokens < max_tokens,
+    ))
+}
+
+#[cfg(feature = "ssr")]
+fn store_inferrence(
+    prompt: String,
+    max_tokens: u32,
+    temperature: f32,
+    tracking_id: Option<Uuid>,
+    origin: Option<&InqueryOrigin>,
+) -> Result<Uuid, ServerFnError<String>> {
+    let id = Uuid::new_v4();
+    let conn = Connection::open("default").map_err(safe_error)?;
+
+    let sql = r#"
+        UPDATE inferrence
+        SET prompt = '', response = '', done = 1
+        WHERE updated_date <= unixepoch() - ? * 86400 AND prompt != '';
+"#;
+    _ = conn
+        .execute(sql, &[Value::Integer(INFERRENCE_TTL_DAYS)])
+        .map_err(safe_error)?;
+
+    let sql = r#"
+        INSERT INTO inferrence
+        (id, tracking_id, prompt, max_tokens, temperature,
+        cache_key, inquery_option, template_version, trimmed,
+        created_date, updated_date)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, unixepoch(), unixepoch())
+"#;
+    _ = conn
+        .execute(
+            sql,
+            &[
+                Value::Blob(Vec::from(id.as_bytes())),
+                tracking_id
+                    .map(|t| Value::Blob(Vec::from(t.as_bytes())))
+                    .unwrap_or(Value::Null),
+                Value::Text(prompt),
+                Value::Integer(max_tokens as i64),
+                Value::Real(temperature as f64),
+                origin
+                    .map(|o| Value::Blob(Vec::from(o.cache_key.as_bytes())))
+                    .unwrap_or(Value::Null),
+                origin
+                    .map(|o| Value::Text(o.option.clone()))
+                    .unwrap_or(Value::Null),
+                origin
+                    .map(|o| Value::Integer(o.template_version as i64))
+                    .unwrap_or(Value::Null),
+                origin
+                    .filter(|o| !o.trimmed.is_empty())
+                    .map(|o| Value::Text(o.trimmed.join(",")))
+                    .unwrap_or(Value::Null),
+            ],
+        )
+        .map_err(safe_error)?;
+
+    Ok(id)
+}
+
+/// stores the prompt of an inquery, the response is generated by [inquire_inferrence_chunk]
+/// unless the same inquery was answered before and `regenerate` is not set
+#[server(InquireInferrence, "/api")]
+pub async fn inquire_inferrence(
+    wk: WorkSheets,
+    lang: Language,
+    tracking_id: Option<Uuid>,
+    regenerate: bool,
+) -> Result<Uuid, ServerFnError<String>> {
+    use crate::app::tracking::complete_inferrence;
+
+    println!("inquire inferrence");
+
+    let mut origin = inquery_origin(&wk, &lang)?;
+    let (prompt, max_tokens, temperature, trimmed) = inferrence_prompt(wk, lang)?;
+    origin.trimmed = trimmed;
+
+    let conn = Connection::open("default").map_err(safe_error)?;
+    let cached = if regenerate {
+        None
+    } else {
+        cached_inferrence(&conn, origin.cache_key)?
+    };
+
+    let id = store_inferrence(
+        prompt.clone(),
+        max_tokens,
+        temperature,
+        tracking_id,
+        Some(&origin),
+    )?;
+
+    if let Some((response, generated_tokens)) = cached {
+        println!("inquire inferrence cached");
+
+        let sql = r#"
+            UPDATE inferrence
+            SET response = ?, generated_tokens = ?, done = 1, updated_date = unixepoch()
+            WHERE id = ?;
+"#;
+        _ = conn
+            .execute(
+                sql,
+                &[
+                    Value::Text(response.clone()),
+                    Value::Integer(generated_tokens),
+                    Value::Blob(Vec::from(id.as_bytes())),
+                ],
+            )
+            .map_err(safe_error)?;
+
+        if let Some(tracking_id) = tracking_id {
+            let filtered = filter_output(prompt.as_str(), response.as_str());
+            _ = complete_inferrence(tracking_id, filtered.text, &filtered.flags);
+        }
+    }
+
+    Ok(id)
+}
+
+/// stores the prompt of a follow-up question about the response,
+/// the earliest messages of the thread are left out when the prompt exceeds the budget
+#[server(InquireFollowUp, "/api")]
+pub async fn inquire_follow_up(
+    wk: WorkSheets,
+    lang: Language,
+    question: String,
+    tracking_id: Option<Uuid>,
+) -> Result<Uuid, ServerFnError<String>> {
+    println!("inquire follow-up");
+
+    let thread = wk.inquire.thread.clone();
+    let (inquery, _, temperature, _) = inferrence_prompt(wk, lang.clone())?;
+    let template = PromptTemplate::load(&lang).map_err(safe_error)?;
+    let question = guard_question(question.as_str())?;
+
+    let budget = PROMPT_BUDGET.saturating_sub(FOLLOW_UP_TOKENS);
+    let mut exchange = thread
+        .into_iter()
+        .map(|m| ChatMessage {
+            text: escape_text(m.text.as_str(), MESSAGE_CAP),
+            ..m
+        })
+        .collect::<Vec<_>>();
+    let mut prompt = template.render_follow_up(inquery.as_str(), &exchange, question.as_str());
+    while estimate_tokens(prompt.as_str()) > budget && !exchange.is_empty() {
+        exchange.remove(0);
+        prompt = template.render_follow_up(inquery.as_str(), &exchange, question.as_str());
+    }
+
+    store_inferrence(prompt, FOLLOW_UP_TOKENS, temperature, tracking_id, None)
+}
+
+#[server(InquireInferrenceChunk, "/api")]
+pub async fn inquire_inferrence_chunk(id: Uuid) -> Result<InferrenceChunk, ServerFnError<String>> {
+    use crate::app::tracking::complete_inferrence;
+
+    let conn = Connection::open("default").map_err(safe_error)?;
+
+    let sql = r#"
+        SELECT tracking_id, prompt, response, max_tokens, generated_tokens, temperature, done,
+            trimmed
+        FROM inferrence
+        WHERE id = ?;
+"#;
+    let data = conn
+        .execute(sql, &[Value::Blob(Vec::from(id.as_bytes()))])
+        .map_err(safe_error)?;
+
+    let row = data
+        .rows()
+        .next()
+        .ok_or(ServerFnError::Request("Not found".to_string()))?;
+
+    let tracking_id = row
+        .get::<&[u8]>("tracking_id")
+        .and_then(|t| Uuid::from_slice(t).ok());
+    let prompt = row.get::<&str>("prompt").unwrap_or_default();
+    let response = row.get::<&str>("response").unwrap_or_default().to_string();
+    let max_tokens = row.get::<i64>("max_tokens").unwrap_or_default() as u32;
+    let generated_tokens = row.get::<i64>("generated_tokens").unwrap_or_default() as u32;
+    let temperature = row.get::<f64>("temperature").unwrap_or_default() as f32;
+    let trimmed = row
+        .get::<&str>("trimmed")
+        .map(|t| t.split(',').map(|f| f.to_string()).collect())
+        .unwrap_or_default();
+
+    if row.get::<i64>("done").unwrap_or_default() != 0 {
+        let filtered = filter_output(prompt, response.as_str());
+        return Ok(InferrenceChunk {
+            text: filtered.text,
+            done: true,
+            flags: filtered.flags,
+            trimmed,
+        });
+    }
+
+    let (text, generated, complete) = infer_chunk(
+        prompt,
+        response.as_str(),
+        CHUNK_TOKENS.min(max_tokens.saturating_sub(generated_tokens)),
+        temperature,
     )
     .map_err(safe_error)?;
 
-    if let Some(tracking_id) = tracking_id {
-        _ = complete_inferrence(tracking_id, response.text.clone());
+    let next_response = format!("{response}{text}");
+    let next_tokens = generated_tokens + generated;
+    let done = complete || next_tokens >= max_tokens;
+
+    // a stopped inferrence keeps the response it was stopped with,
+    // a chunk generated by a concurrent poll in the meantime wins over this one
+    let sql = r#"
+        UPDATE inferrence
+        SET response = ?, generated_tokens = ?, done = ?, updated_date = unixepoch()
+        WHERE id = ? AND done = 0 AND generated_tokens = ?
+        RETURNING id, cache_key;
+"#;
+    let updated = conn
+        .execute(
+            sql,
+            &[
+                Value::Text(next_response.clone()),
+                Value::Integer(next_tokens as i64),
+                Value::Integer(done as i64),
+                Value::Blob(Vec::from(id.as_bytes())),
+                Value::Integer(generated_tokens as i64),
+            ],
+        )
+        .map_err(safe_error)?;
+
+    let Some(row) = updated.rows().next() else {
+        // the next poll reads the chunk stored by the other one
+        let filtered = filter_output(prompt, response.as_str());
+        return Ok(InferrenceChunk {
+            text: filtered.text,
+            done: false,
+            flags: filtered.flags,
+            trimmed,
+        });
+    };
+
+    // the stored response is kept as generated to continue it
+    let filtered = filter_output(prompt, next_response.as_str());
+
+    // only complete responses without flags are served again
+    let cache_key = row
+        .get::<&[u8]>("cache_key")
+        .and_then(|k| Uuid::from_slice(k).ok());
+    if let (true, true, Some(cache_key)) = (done, filtered.flags.is_empty(), cache_key) {
+        _ = cache_inferrence(&conn, cache_key, next_response, next_tokens);
+    }
+
+    if let (true, Some(tracking_id)) = (done, tracking_id) {
+        _ = complete_inferrence(tracking_id, filtered.text.clone(), &filtered.flags);
     }
 
-    Ok(response.text)
+    Ok(InferrenceChunk {
+        text: filtered.text,
+        done,
+        flags: filtered.flags,
+        trimmed,
+    })
+}
+
+#[server(StopInferrence, "/api")]
+pub async fn stop_inferrence(id: Uuid) -> Result<(), ServerFnError<String>> {
+    use crate::app::tracking::complete_inferrence;
+
+    let conn = Connection::open("default").map_err(safe_error)?;
+
+    let sql = r#"
+        UPDATE inferrence
+        SET done = 1, updated_date = unixepoch()
+        WHERE id = ? AND done = 0
+        RETURNING tracking_id, prompt, response;
+"#;
+    let data = conn
+        .execute(sql, &[Value::Blob(Vec::from(id.as_bytes()))])
+        .map_err(safe_error)?;
+
+    if let Some(row) = data.rows().next() {
+        let tracking_id = row
+            .get::<&[u8]>("tracking_id")
+            .and_then(|t| Uuid::from_slice(t).ok());
+        if let Some(tracking_id) = tracking_id {
+            let filtered = filter_output(
+                row.get::<&str>("prompt").unwrap_or_default(),
+                row.get::<&str>("response").unwrap_or_default(),
+            );
+            _ = complete_inferrence(tracking_id, filtered.text, &filtered.flags);
+        }
+    }
+
+    Ok(())
 }
 
 #[server(InquirePersonal, "/api")]
@@ -0,0 +1,479 @@
+#![cfg_attr(not(feature = "ssr"), allow(unused))]
+
+use std::{fmt::Display, time::Duration};
+
+use aes_gcm::{
+    aead::{Aead, AeadCore, OsRng},
+    Aes256Gcm, KeyInit, Nonce,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use form_signal::FormState;
+use leptos::{error::Error, *};
+use uuid::Uuid;
+
+use crate::app::{
+    components::{
+        use_wk_state, ButtonView, CheckedOption, ErrorView, IconView, ModalView, RadioInputView,
+        Status, StatusView, StringInputView,
+    },
+    state::WorkSheets,
+    use_lang,
+    util::copy_to_clipboard,
+};
+
+#[cfg(feature = "ssr")]
+use crate::server::safe_error;
+#[cfg(feature = "ssr")]
+use spin_sdk::sqlite::{Connection, Value};
+
+/// largest snapshot stored by [share_worksheets], in bytes of json
+pub const SHARED_WK_MAX_LEN: usize = 256 * 1024;
+
+/// stores a read-only snapshot of the worksheets
+///
+/// returns the slug of the snapshot and a token to revoke it
+#[server(ShareWorksheets, "/api")]
+pub async fn share_worksheets(
+    wk: WorkSheets,
+    expires_in_days: Option<u32>,
+) -> Result<(String, Uuid), ServerFnError<String>> {
+    let slug = Uuid::new_v4().simple().to_string();
+    let revoke_token = Uuid::new_v4();
+
+    // never share personal data
+    let wk = WorkSheets {
+        inquire: Default::default(),
+        ..wk
+    };
+    let data = serde_json::to_string(&wk).map_err(safe_error)?;
+    if data.len() > SHARED_WK_MAX_LEN {
+        return Err(ServerFnError::Request(
+            "Worksheets are too large to share".to_string(),
+        ));
+    }
+
+    let conn = Connection::open("default").map_err(safe_error)?;
+
+    // expired snapshots are purged whenever a new one is stored
+    _ = conn
+        .execute(
+            "DELETE FROM shared_wk WHERE expires_date <= unixepoch();",
+            &[],
+        )
+        .map_err(safe_error)?;
+
+    let sql = r#"
+        INSERT INTO shared_wk
+        (slug, wk, revoke_token, created_date, expires_date)
+        VALUES (?, ?, ?, unixepoch(), unixepoch() + ? * 86400)
+"#;
+    _ = conn
+        .execute(
+            sql,
+            &[
+                Value::Text(slug.clone()),
+                Value::Text(data),
+                Value::Blob(Vec::from(revoke_token.as_bytes())),
+                expires_in_days
+                    .map(|d| Value::Integer(d as i64))
+                    .unwrap_or(Value::Null),
+            ],
+        )
+        .map_err(safe_error)?;
+
+    println!("shared worksheets {slug}");
+
+    Ok((slug, revoke_token))
+}
+
+#[server(GetSharedWorksheets, "/api")]
+pub async fn get_shared_worksheets(slug: String) -> Result<WorkSheets, ServerFnError<String>> {
+    let conn = Connection::open("default").map_err(safe_error)?;
+
+    let sql = r#"
+        SELECT wk FROM shared_wk
+        WHERE slug = ?
+            AND (expires_date IS NULL OR expires_date > unixepoch());
+"#;
+    let data = conn
+        .execute(sql, &[Value::Text(slug)])
+        .map_err(safe_error)?;
+
+    let wk = data
+        .rows()
+        .next()
+        .and_then(|row| row.get::<&str>("wk").map(|s| s.to_string()))
+        .ok_or(ServerFnError::Request("Not found".to_string()))?;
+
+    serde_json::from_str(wk.as_str()).map_err(|e| ServerFnError::ServerError(safe_error(e)))
+}
+
+#[server(RevokeSharedWorksheets, "/api")]
+pub async fn revoke_shared_worksheets(
+    slug: String,
+    revoke_token: Uuid,
+) -> Result<(), ServerFnError<String>> {
+    let conn = Connection::open("default").map_err(safe_error)?;
+
+    let sql = r#"
+        DELETE FROM shared_wk
+        WHERE slug = ? AND revoke_token = ?
+        RETURNING slug;
+"#;
+    let data = conn
+        .execute(
+            sql,
+            &[
+                Value::Text(slug),
+                Value::Blob(Vec::from(revoke_token.as_bytes())),
+            ],
+        )
+        .map_err(safe_error)?;
+
+    if data.rows().next().is_some() {
+        Ok(())
+    } else {
+        Err(ServerFnError::Request("Not found".to_string()))
+    }
+}
+
+/// upper bound for decompressed private links
+const PRIVATE_LINK_LIMIT: usize = 1 << 20;
+
+const NONCE_LEN: usize = 12;
+
+/// longest fragment of a private link, longer links break in mail and chat clients
+pub const PRIVATE_FRAGMENT_MAX_LEN: usize = 16 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrivateLinkError {
+    /// the link carries no worksheets
+    Missing,
+    TooLong,
+    Malformed,
+}
+
+impl Display for PrivateLinkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrivateLinkError::Missing => write!(f, "Not found"),
+            PrivateLinkError::TooLong => write!(f, "Worksheets are too large for a private link"),
+            PrivateLinkError::Malformed => write!(f, "Malformed link"),
+        }
+    }
+}
+
+impl std::error::Error for PrivateLinkError {}
+
+/// compresses and encrypts the worksheets for the url fragment
+///
+/// the fragment holds `<nonce + ciphertext>.<key>`, fragments are never sent to the server
+pub fn seal_worksheets(wk: &WorkSheets) -> Result<String, PrivateLinkError> {
+    // never share personal data
+    let wk = WorkSheets {
+        inquire: Default::default(),
+        ..wk.clone()
+    };
+    let data = serde_json::to_vec(&wk).map_err(|_| PrivateLinkError::Malformed)?;
+    let compressed = miniz_oxide::deflate::compress_to_vec(data.as_slice(), 9);
+
+    let key = Aes256Gcm::generate_key(OsRng);
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+    let encrypted = Aes256Gcm::new(&key)
+        .encrypt(&nonce, compressed.as_slice())
+        .map_err(|_| PrivateLinkError::Malformed)?;
+
+    let payload = [nonce.as_slice(), encrypted.as_slice()].concat();
+
+    let fragment = format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(payload),
+        URL_SAFE_NO_PAD.encode(key.as_slice())
+    );
+
+    if fragment.len() > PRIVATE_FRAGMENT_MAX_LEN {
+        Err(PrivateLinkError::TooLong)
+    } else {
+        Ok(fragment)
+    }
+}
+
+/// decrypts and decompresses worksheets sealed with [seal_worksheets]
+pub fn open_worksheets(fragment: &str) -> Result<WorkSheets, PrivateLinkError> {
+    let fragment = fragment.trim_start_matches('#');
+    if fragment.len() > PRIVATE_FRAGMENT_MAX_LEN {
+        return Err(PrivateLinkError::TooLong);
+    }
+    let (payload, key) = fragment.split_once('.').ok_or(PrivateLinkError::Missing)?;
+
+    let payload = URL_SAFE_NO_PAD
+        .decode(payload)
+        .map_err(|_| PrivateLinkError::Malformed)?;
+    let key = URL_SAFE_NO_PAD
+        .decode(key)
+        .map_err(|_| PrivateLinkError::Malformed)?;
+
+    if payload.len() <= NONCE_LEN {
+        return Err(PrivateLinkError::Malformed);
+    }
+    let (nonce, encrypted) = payload.split_at(NONCE_LEN);
+
+    let compressed = Aes256Gcm::new_from_slice(key.as_slice())
+        .map_err(|_| PrivateLinkError::Malformed)?
+        .decrypt(Nonce::from_slice(nonce), encrypted)
+        .map_err(|_| PrivateLinkError::Malformed)?;
+    let data = miniz_oxide::inflate::decompress_to_vec_with_limit(&compressed, PRIVATE_LINK_LIMIT)
+        .map_err(|_| PrivateLinkError::Malformed)?;
+
+    serde_json::from_slice(data.as_slice()).map_err(|_| PrivateLinkError::Malformed)
+}
+
+const EXPIRY_OPTIONS: &[Option<u32>] = &[Some(1), Some(7), Some(30), None];
+
+const SHARE_PRIVATE: &str = "private";
+
+#[component]
+pub fn ShareView() -> impl IntoView {
+    let state = use_wk_state();
+    let lang = use_lang();
+    let (show_share, set_show_share) = create_signal(false);
+    let (copied, set_copied) = create_signal(false);
+    let mode = create_rw_signal(FormState::new(String::default()));
+    let expiry = create_rw_signal(FormState::new("7".to_string()));
+    let private_link = create_rw_signal(None::<Result<String, PrivateLinkError>>);
+
+    let is_private = Signal::derive(move || mode.get().get() == SHARE_PRIVATE);
+
+    let share_action = create_action(|data: &(WorkSheets, Option<u32>)| {
+        let (wk, expires_in_days) = data.clone();
+        async move {
+            share_worksheets(wk, expires_in_days)
+                .await
+                .map_err(ServerFnErrorErr::from)
+        }
+    });
+    let revoke_action = create_action(|data: &(String, Uuid)| {
+        let (slug, revoke_token) = data.clone();
+        async move {
+            revoke_shared_worksheets(slug, revoke_token)
+                .await
+                .map_err(ServerFnErrorErr::from)
+        }
+    });
+
+    let mode_options = Signal::derive(|| {
+        vec![
+            CheckedOption {
+                value: String::default(),
+                label: view! {
+                    <p class="font-bold">{t!("worksheets.share.mode_server")}</p>
+                    <p class="text-sm">{t!("worksheets.share.mode_server_hint")}</p>
+                }
+                .into_view(),
+            },
+            CheckedOption {
+                value: SHARE_PRIVATE.to_string(),
+                label: view! {
+                    <p class="font-bold">{t!("worksheets.share.mode_private")}</p>
+                    <p class="text-sm">{t!("worksheets.share.mode_private_hint")}</p>
+                }
+                .into_view(),
+            },
+        ]
+    });
+
+    let expiry_options = Signal::derive(|| {
+        EXPIRY_OPTIONS
+            .iter()
+            .map(|days| {
+                let (value, label) = match days {
+                    Some(days) => (
+                        days.to_string(),
+                        t!("worksheets.share.expires_in", days = days).to_string(),
+                    ),
+                    None => (
+                        String::default(),
+                        t!("worksheets.share.no_expiry").to_string(),
+                    ),
+                };
+                CheckedOption {
+                    value,
+                    label: view! { <p>{label}</p> }.into_view(),
+                }
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let shared_link = Signal::derive(move || {
+        let origin = window().location().origin().unwrap_or_default();
+        if let Some(Ok(fragment)) = private_link.get() {
+            Some(format!("{origin}/{}/process/shared#{fragment}", lang.get()))
+        } else {
+            share_action
+                .value()
+                .get()
+                .and_then(|r| r.ok())
+                .map(|(slug, _)| format!("{origin}/{}/process/shared/{slug}", lang.get()))
+        }
+    });
+
+    let shared_link_value =
+        Signal::derive(move || FormState::new(shared_link.get().unwrap_or_default()));
+
+    // the token only lives in this link, the owner keeps it to revoke the snapshot later
+    let revoke_link_value = Signal::derive(move || {
+        let origin = window().location().origin().unwrap_or_default();
+        FormState::new(
+            share_action
+                .value()
+                .get()
+                .and_then(|r| r.ok())
+                .map(|(slug, token)| {
+                    format!(
+                        "{origin}/{}/process/shared/{slug}?revoke={token}",
+                        lang.get()
+                    )
+                })
+                .unwrap_or_default(),
+        )
+    });
+
+    let on_share = move |_| {
+        let wk: WorkSheets = state.get().get();
+        if is_private.get() {
+            private_link.set(Some(seal_worksheets(&wk)));
+        } else {
+            let expires_in_days = expiry.get().get().parse().ok();
+            share_action.dispatch((wk, expires_in_days));
+        }
+    };
+
+    let on_copy = move |_| {
+        if let Some(link) = shared_link.get() {
+            copy_to_clipboard(link.as_str());
+            set_copied.set(true);
+            set_timeout(move || set_copied.set(false), Duration::from_secs(3));
+        }
+    };
+
+    let on_revoke = move |_| {
+        if let Some(Ok((slug, revoke_token))) = share_action.value().get() {
+            revoke_action.dispatch((slug, revoke_token));
+        }
+    };
+
+    let on_resolve = Callback::new(move |_| {
+        set_show_share.set(false);
+        // private links can't be revoked, a new one is created every time
+        private_link.set(None);
+        if revoke_action.value().get().is_some() {
+            share_action.value().set(None);
+            revoke_action.value().set(None);
+        }
+    });
+
+    let link_view = move || {
+        view! {
+            <StringInputView
+                input_type="url"
+                value=shared_link_value
+                attr:readonly=true
+            />
+            <Show when=move || !is_private.get()>
+                <p class="mt-4 mb-2 text-sm">{t!("worksheets.share.revoke_link")}</p>
+                <StringInputView
+                    input_type="url"
+                    value=revoke_link_value
+                    attr:readonly=true
+                />
+            </Show>
+            <div class="flex w-full mt-4 gap-4 justify-center">
+                <ButtonView
+                    cta=2
+                    on:click=on_copy
+                >
+                    {move || if copied.get() {
+                        t!("worksheets.download.copied").to_string()
+                    } else {
+                        t!("worksheets.share.copy").to_string()
+                    }}
+                </ButtonView>
+                <Show when=move || !is_private.get()>
+                    <ButtonView
+                        cta=-1
+                        on:click=on_revoke
+                        disabled=revoke_action.pending()
+                    >
+                        <IconView icon="Delete"/>
+                        {t!("worksheets.share.revoke")}
+                    </ButtonView>
+                </Show>
+            </div>
+        }
+        .into_view()
+    };
+
+    view! {
+        <ButtonView
+            cta=1
+            on:click=move |_| set_show_share.set(true)
+        >
+            <IconView icon="Send"/>
+            {t!("worksheets.share.cta")}
+        </ButtonView>
+        <ModalView
+            curtain=true
+            when=show_share
+            on_resolve
+        >
+            <h3 class="text-xl mb-2">{t!("worksheets.share.cta")}</h3>
+            <div class="max-w-prose mb-4 whitespace-pre-line">
+                <p>
+                    {move || if is_private.get() {
+                        t!("worksheets.share.description_private").to_string()
+                    } else {
+                        t!("worksheets.share.description").to_string()
+                    }}
+                </p>
+            </div>
+            <ErrorBoundary fallback=|err| view! { <ErrorView errors=err/>}>
+                {move || match (private_link.get(), share_action.value().get(), revoke_action.value().get()) {
+                    (Some(Err(PrivateLinkError::TooLong)), _, _) => Ok(view!{
+                        <p class="max-w-prose text-center">{t!("worksheets.share.too_long")}</p>
+                    }.into_view()),
+                    (Some(r), _, _) => r.map(|_| link_view()).map_err(Error::from),
+                    (None, _, Some(r)) => r.map(|_| view!{
+                        <StatusView
+                            status=Status::Success
+                            message={t!("worksheets.share.revoked").to_string()}
+                            attr:class="mx-auto"
+                        />
+                    }.into_view()).map_err(Error::from),
+                    (None, Some(r), None) => r.map(|_| link_view()).map_err(Error::from),
+                    (None, None, None) => Ok(view!{
+                        <RadioInputView options=mode_options value=mode/>
+                        <Show when=move || !is_private.get()>
+                            <p class="mt-4 mb-2">{t!("worksheets.share.label_expiry")}</p>
+                            <RadioInputView options=expiry_options value=expiry/>
+                        </Show>
+                        <div class="flex w-full mt-4 justify-center">
+                            <Show
+                                when=move || !share_action.pending().get()
+                                fallback=|| view!{ <StatusView status=Status::Pending/> }
+                            >
+                                <ButtonView
+                                    cta=2
+                                    on:click=on_share
+                                >
+                                    <IconView icon="Send"/>
+                                    {t!("worksheets.share.create")}
+                                </ButtonView>
+                            </Show>
+                        </div>
+                    }.into_view()),
+                }}
+            </ErrorBoundary>
+        </ModalView>
+    }
+}
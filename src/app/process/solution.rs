@@ -4,15 +4,18 @@ use leptos_router::*;
 use uuid::Uuid;
 use web_time::Instant;
 
-use form_signal::FormState;
-
 use crate::app::{
     components::{
-        use_example_ctx, use_wk_ctx, use_wk_state, ButtonSize, ButtonView, DescriptionView,
-        HistoryEntry, ListInputView, ReadOnlyListView, ReadOnlyView, UndoRemove, WorksheetHeader,
+        use_example_ctx, use_example_shared, use_wk_ctx, use_wk_state, ButtonSize, ButtonView,
+        DescriptionView, HistoryEntry, ListInputView, ModalView, ReadOnlyListView, ReadOnlyView,
+        UndoRemove, WorksheetHeader,
+    },
+    next_step_link,
+    process::{FixedProblemStatement, SolutionMatrixView, SuggestTarget, SuggestView},
+    state::{
+        keyed_values, ChoiceList, Completenes, ProcessStep, RemovedEntry, SolutionRating,
+        SolutionsWKFormState,
     },
-    process::FixedProblemStatement,
-    state::{Completenes, ProcessStep},
     tabs_signal, use_lang,
 };
 
@@ -21,8 +24,7 @@ use crate::app::{
 pub fn SolutionView() -> impl IntoView {
     let wk_state = use_wk_state();
     let wk_ctx = use_wk_ctx();
-    let lang = use_lang();
-    let link = Signal::derive(move || format!("/{}/process/3", lang.get()));
+    let link = next_step_link(ProcessStep::Solution);
 
     let solution_delete_history = create_rw_signal(vec![]);
 
@@ -35,29 +37,76 @@ pub fn SolutionView() -> impl IntoView {
             .unwrap_or_default()
     });
     let solutions_value_add = move |(next, index): (String, Option<usize>)| {
-        let next = FormState::new(next);
+        let next = SolutionsWKFormState::solutions_entry(Uuid::new_v4(), next);
         let id = next.id;
         wk_state.get().solutions.update(move |p| {
             p.solutions.insert(index.unwrap_or(p.solutions.len()), next);
         });
         id
     };
-    let solutions_value_remove = move |id: Uuid| {
+    let remove_solution = move |id: Uuid| {
+        let solutions = wk_state.get().solutions.get_untracked();
+        let rating = solutions
+            .ratings
+            .get_untracked()
+            .into_iter()
+            .find(|r| r.id == id);
+        solutions.forget_rating(id);
+        let compromise = wk_state.get().compromise.get_untracked();
+        let chosen = compromise.is_chosen(ChoiceList::Solutions, id);
+        compromise.forget_choice(ChoiceList::Solutions, id);
         wk_state.get().solutions.update(move |p| {
             let i = p.solutions.iter().position(|v| v.id == id).unwrap();
-            let removed = p.solutions.remove(i).get_untracked();
+            let removed = p.solutions.remove(i);
+            let removed = RemovedEntry {
+                id,
+                value: removed.get_untracked(),
+                chosen,
+                attached: rating,
+            };
             solution_delete_history.update(|h| h.push((removed, i, Instant::now())));
         })
     };
-    let solution_restore = move |(val, at, _): HistoryEntry<String>| {
-        wk_state.get().solutions.update(move |p| {
-            if p.solutions.len() >= at {
-                p.solutions.insert(at, FormState::new(val));
-            } else {
-                p.solutions.push(FormState::new(val));
-            }
-        })
+    let (pending_removal, set_pending_removal) = create_signal(None::<Uuid>);
+    let solutions_value_remove = move |id: Uuid| {
+        if wk_state
+            .get()
+            .compromise
+            .get_untracked()
+            .is_chosen(ChoiceList::Solutions, id)
+        {
+            set_pending_removal.set(Some(id));
+        } else {
+            remove_solution(id);
+        }
     };
+    let on_confirm_removal = Callback::new(move |confirmed: bool| {
+        if let Some(id) = pending_removal.get_untracked().filter(|_| confirmed) {
+            remove_solution(id);
+        }
+        set_pending_removal.set(None);
+    });
+    let solution_restore =
+        move |(removed, at, _): HistoryEntry<RemovedEntry<String, Option<SolutionRating>>>| {
+            if let Some(rating) = removed.attached {
+                wk_state
+                    .get()
+                    .solutions
+                    .get_untracked()
+                    .update_rating(removed.id, |r| *r = rating);
+            }
+            if removed.chosen {
+                wk_state
+                    .get()
+                    .compromise
+                    .get_untracked()
+                    .choose(ChoiceList::Solutions, removed.id);
+            }
+            wk_state.get().solutions.update(move |p| {
+                let entry = SolutionsWKFormState::solutions_entry(removed.id, removed.value);
+                p.solutions.insert(at.min(p.solutions.len()), entry);
+            })
+        };
 
     let tabs = tabs_signal(ProcessStep::Solution);
 
@@ -84,7 +133,7 @@ pub fn SolutionView() -> impl IntoView {
                     <p>{t!("worksheets.solutions.instruction")}</p>
                 </div>
                 <FixedProblemStatement/>
-                <div class="grid">
+                <div class="grid" id="solutions-solutions">
                     <h4 class="text-center text-xl mb-4">
                         {t!("worksheets.solutions.label_solutions")}
                     </h4>
@@ -96,7 +145,14 @@ pub fn SolutionView() -> impl IntoView {
                         add_entry_text={t!("worksheets.solutions.add_solution").to_string()}
                         placeholder={t!("worksheets.solutions.placeholder_solution").to_string()}
                     />
+                    <SuggestView
+                        target=SuggestTarget::Solutions
+                        on_accept=move |value: String| {
+                            solutions_value_add((value, None));
+                        }
+                    />
                 </div>
+                <SolutionMatrixView/>
             </form>
             <div class="flex w-full mt-8 justify-center">
                 <ButtonView
@@ -113,6 +169,13 @@ pub fn SolutionView() -> impl IntoView {
             history=solution_delete_history
             on_restore=solution_restore
         />
+        <ModalView
+            when=Signal::derive(move || pending_removal.get().is_some())
+            cancel_btn=true
+            on_resolve=on_confirm_removal
+        >
+            <p class="max-w-prose mb-4 whitespace-pre-line">{t!("worksheets.compromise.confirm_remove")}</p>
+        </ModalView>
     }
 }
 
@@ -120,13 +183,18 @@ pub fn SolutionView() -> impl IntoView {
 pub fn ExampleSolutionView() -> impl IntoView {
     let lang = use_lang();
     let (wk, example) = use_example_ctx();
+    let shared = use_example_shared();
     let wk_ctx = use_wk_ctx();
 
-    let tabs = tabs_signal(ProcessStep::Solution);
+    let tabs = if shared {
+        Signal::derive(Vec::new)
+    } else {
+        tabs_signal(ProcessStep::Solution)
+    };
 
     let problem_statement = Signal::derive(move || wk.get().problem.problem_statement);
 
-    let solutions_data = Signal::derive(move || wk.get().solutions.solutions);
+    let solutions_data = Signal::derive(move || keyed_values(&wk.get().solutions.solutions));
 
     let title = Signal::derive(move || {
         t!(
@@ -159,9 +227,11 @@ pub fn ExampleSolutionView() -> impl IntoView {
                 <p class="whitespace-pre-line">
                     {move || example.get().description}
                 </p>
-                <A href=case_href attr:class="underline">
-                    {move || t!("worksheets.view_example", title=example.get().title)}
-                </A>
+                <Show when=move || !shared>
+                    <A href=case_href attr:class="underline">
+                        {move || t!("worksheets.view_example", title=example.get().title)}
+                    </A>
+                </Show>
             </DescriptionView>
             <div role="form">
                 <div class="max-w-prose mb-4 whitespace-pre-line italic">
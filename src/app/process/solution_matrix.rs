@@ -0,0 +1,254 @@
+use leptos::*;
+use uuid::Uuid;
+
+use crate::app::{
+    components::use_wk_state,
+    state::{ProblemWK, SolutionRating, SolutionsWK, RATING_SCALE},
+};
+
+const CHART_SIZE: f32 = 300.0;
+
+/// rating select of a single criterion
+#[component]
+fn RatingSelect(
+    #[prop(into)] label: String,
+    #[prop(into)] value: Signal<Option<u8>>,
+    #[prop(into)] on_change: Callback<Option<u8>>,
+) -> impl IntoView {
+    let on_change = move |ev| {
+        let next = event_target_value(&ev).parse::<u8>().ok();
+        on_change.call(next);
+    };
+
+    view! {
+        <select
+            aria-label=label
+            on:change=on_change
+            prop:value=move || value.get().map(|v| v.to_string()).unwrap_or_default()
+            class="px-2 py-1 rounded border border-slate-400 bg-stone-50 dark:bg-stone-950 text-stone-950 dark:text-stone-50"
+        >
+            <option value="">"–"</option>
+            {(1..=RATING_SCALE)
+                .map(|r| view! { <option value=r.to_string()>{r}</option> })
+                .collect_view()}
+        </select>
+    }
+}
+
+/// scoring matrix of the solutions against impact, effort, risk and the stakeholders
+#[component]
+pub fn SolutionMatrixView() -> impl IntoView {
+    let wk_state = use_wk_state();
+
+    let solutions = Signal::derive(move || {
+        let solutions: SolutionsWK = (&wk_state.get().solutions.get()).into();
+        solutions
+    });
+    let stakeholders = Signal::derive(move || {
+        let problem: ProblemWK = (&wk_state.get().problem.get()).into();
+        problem.unique_stakeholder_entries()
+    });
+    let rows = Signal::derive(move || {
+        let solutions = solutions.get();
+        solutions
+            .solutions
+            .iter()
+            .filter(|(_, s)| !s.is_empty())
+            .map(|(id, s)| (solutions.rating(*id), s.clone()))
+            .collect::<Vec<_>>()
+    });
+
+    let criterion = move |id: Uuid,
+                          label: String,
+                          get: fn(&SolutionRating) -> Option<u8>,
+                          set: fn(&mut SolutionRating, Option<u8>)| {
+        let value = Signal::derive(move || get(&solutions.get().rating(id)));
+        let on_change = Callback::new(move |v: Option<u8>| {
+            wk_state
+                .get_untracked()
+                .solutions
+                .get_untracked()
+                .update_rating(id, |r| set(r, v))
+        });
+        view! {
+            <td class="p-2 text-center">
+                <RatingSelect label value on_change/>
+            </td>
+        }
+    };
+
+    let row = move |(rating, name): (SolutionRating, String)| {
+        let id = rating.id;
+        view! {
+            <tr class="border-t border-slate-400">
+                <th scope="row" class="p-2 text-left font-normal">{name.clone()}</th>
+                {criterion(
+                    id,
+                    format!("{name}: {}", t!("worksheets.solutions.matrix.impact")),
+                    |r| r.impact,
+                    |r, v| r.impact = v,
+                )}
+                {criterion(
+                    id,
+                    format!("{name}: {}", t!("worksheets.solutions.matrix.effort")),
+                    |r| r.effort,
+                    |r, v| r.effort = v,
+                )}
+                {criterion(
+                    id,
+                    format!("{name}: {}", t!("worksheets.solutions.matrix.risk")),
+                    |r| r.risk,
+                    |r, v| r.risk = v,
+                )}
+                {move || stakeholders
+                    .get()
+                    .into_iter()
+                    .map(|(s_id, s_name)| {
+                        let value = Signal::derive(move || solutions.get().rating(id).stakeholder(s_id));
+                        let on_change = Callback::new(move |v: Option<u8>| {
+                            wk_state
+                                .get_untracked()
+                                .solutions
+                                .get_untracked()
+                                .update_rating(id, |r| r.set_stakeholder(s_id, v))
+                        });
+                        view! {
+                            <td class="p-2 text-center">
+                                <RatingSelect label=format!("{name}: {s_name}") value on_change/>
+                            </td>
+                        }
+                    })
+                    .collect_view()}
+            </tr>
+        }
+    };
+
+    view! {
+        <div id="solutions-ratings" class="mt-8">
+            <h4 class="text-center text-xl mb-4">
+                {t!("worksheets.solutions.matrix.title")}
+            </h4>
+            <div class="max-w-prose mb-4 whitespace-pre-line">
+                <p>{t!("worksheets.solutions.matrix.instruction")}</p>
+            </div>
+            <div class="overflow-x-auto">
+                <table class="w-full">
+                    <thead>
+                        <tr>
+                            <th scope="col" class="p-2 text-left">{t!("worksheets.solutions.label_solutions")}</th>
+                            <th scope="col" class="p-2">{t!("worksheets.solutions.matrix.impact")}</th>
+                            <th scope="col" class="p-2">{t!("worksheets.solutions.matrix.effort")}</th>
+                            <th scope="col" class="p-2">{t!("worksheets.solutions.matrix.risk")}</th>
+                            {move || stakeholders
+                                .get()
+                                .into_iter()
+                                .map(|(_, s)| view! { <th scope="col" class="p-2">{s.name}</th> })
+                                .collect_view()}
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {move || rows.get().into_iter().map(row).collect_view()}
+                    </tbody>
+                </table>
+            </div>
+            <SolutionChartView/>
+        </div>
+    }
+}
+
+/// impact versus effort chart of the rated solutions, risk sets the size of a mark
+#[component]
+pub fn SolutionChartView() -> impl IntoView {
+    let wk_state = use_wk_state();
+
+    let rated = Signal::derive(move || {
+        let solutions: SolutionsWK = (&wk_state.get().solutions.get()).into();
+        solutions
+            .ranked_solutions()
+            .into_iter()
+            .map(|(id, name)| (solutions.rating(id), name))
+            .filter(|(r, _)| r.is_rated())
+            .collect::<Vec<_>>()
+    });
+
+    let position = |level: u8| {
+        let step = CHART_SIZE / RATING_SCALE as f32;
+        step * (level as f32 - 0.5)
+    };
+
+    let quadrant = move |x: f32, y: f32, key: &'static str| {
+        view! {
+            <text
+                x=x
+                y=y
+                text-anchor="middle"
+                class="fill-current text-xs opacity-50"
+            >
+                {t!(key)}
+            </text>
+        }
+    };
+
+    view! {
+        <Show when=move || !rated.get().is_empty()>
+            <figure class="flex flex-wrap justify-center gap-6 mt-6">
+                <svg
+                    viewBox=format!("-24 0 {} {}", CHART_SIZE + 24.0, CHART_SIZE + 24.0)
+                    class="w-full max-w-sm"
+                    role="img"
+                    aria-label=t!("worksheets.solutions.matrix.chart").to_string()
+                >
+                    <g class="stroke-slate-400" stroke-width="1">
+                        <rect x="0" y="0" width=CHART_SIZE height=CHART_SIZE fill="none"/>
+                        <line x1=CHART_SIZE / 2.0 y1="0" x2=CHART_SIZE / 2.0 y2=CHART_SIZE/>
+                        <line x1="0" y1=CHART_SIZE / 2.0 x2=CHART_SIZE y2=CHART_SIZE / 2.0/>
+                    </g>
+                    {quadrant(CHART_SIZE * 0.25, 16.0, "worksheets.solutions.matrix.quick_wins")}
+                    {quadrant(CHART_SIZE * 0.75, 16.0, "worksheets.solutions.matrix.major_projects")}
+                    {quadrant(CHART_SIZE * 0.25, CHART_SIZE - 8.0, "worksheets.solutions.matrix.fill_ins")}
+                    {quadrant(CHART_SIZE * 0.75, CHART_SIZE - 8.0, "worksheets.solutions.matrix.thankless")}
+                    <text x=CHART_SIZE / 2.0 y=CHART_SIZE + 18.0 text-anchor="middle" class="fill-current text-sm">
+                        {t!("worksheets.solutions.matrix.effort")}
+                    </text>
+                    <text
+                        x="-8"
+                        y=CHART_SIZE / 2.0
+                        text-anchor="middle"
+                        transform=format!("rotate(-90 -8 {})", CHART_SIZE / 2.0)
+                        class="fill-current text-sm"
+                    >
+                        {t!("worksheets.solutions.matrix.impact")}
+                    </text>
+                    {move || rated
+                        .get()
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, (rating, name))| {
+                            let cx = position(rating.effort.unwrap_or_default());
+                            let cy = CHART_SIZE - position(rating.impact.unwrap_or_default());
+                            let r = 8.0 + 2.0 * rating.risk.unwrap_or_default() as f32;
+                            view! {
+                                <g>
+                                    <title>{name}</title>
+                                    <circle cx=cx cy=cy r=r class="fill-purple-400 opacity-75"/>
+                                    <text x=cx y=cy + 4.0 text-anchor="middle" class="fill-current text-xs">
+                                        {i + 1}
+                                    </text>
+                                </g>
+                            }
+                        })
+                        .collect_view()}
+                </svg>
+                <figcaption>
+                    <ol class="list-decimal list-inside">
+                        {move || rated
+                            .get()
+                            .into_iter()
+                            .map(|(_, name)| view! { <li>{name}</li> })
+                            .collect_view()}
+                    </ol>
+                </figcaption>
+            </figure>
+        </Show>
+    }
+}
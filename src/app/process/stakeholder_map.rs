@@ -0,0 +1,188 @@
+use form_signal::FormState;
+use leptos::*;
+use uuid::Uuid;
+
+use crate::app::{
+    components::{use_wk_state, ButtonSize, ButtonView, StringInputView},
+    state::{ProblemWK, Stakeholder, STAKEHOLDER_LEVELS},
+};
+
+/// power/interest grid of the stakeholders
+///
+/// a selected stakeholder is placed by choosing a cell of the grid
+#[component]
+pub fn StakeholderMapView() -> impl IntoView {
+    let wk_state = use_wk_state();
+    let (selected, set_selected) = create_signal(None::<Uuid>);
+    let role = create_rw_signal(FormState::new(String::default()));
+
+    let problem = Signal::derive(move || {
+        let problem: ProblemWK = (&wk_state.get().problem.get()).into();
+        problem
+    });
+
+    let stakeholders = Signal::derive(move || problem.get().unique_stakeholder_entries());
+
+    let selected_name = Signal::derive(move || {
+        let id = selected.get()?;
+        stakeholders
+            .get()
+            .into_iter()
+            .find(|(s_id, _)| *s_id == id)
+            .map(|(_, s)| s.name)
+    });
+
+    create_effect(move |_| {
+        let value = selected
+            .get()
+            .map(|id| problem.get_untracked().stakeholder(id).role)
+            .unwrap_or_default();
+        role.get_untracked().set(value);
+    });
+
+    create_effect(move |_| {
+        let value = role.get().get();
+        if let Some(id) = selected.get_untracked() {
+            if problem.get_untracked().stakeholder(id).role != value {
+                wk_state
+                    .get_untracked()
+                    .problem
+                    .get_untracked()
+                    .update_stakeholder(id, |s| s.role = value);
+            }
+        }
+    });
+
+    let on_place = move |influence: u8, interest: u8| {
+        if let Some(id) = selected.get_untracked() {
+            wk_state
+                .get_untracked()
+                .problem
+                .get_untracked()
+                .update_stakeholder(id, |s| {
+                    s.influence = Some(influence);
+                    s.interest = Some(interest);
+                });
+        }
+    };
+
+    let chip = move |id: Uuid, stakeholder: Stakeholder| {
+        let class = move || {
+            format!(
+                "px-2 py-1 rounded border border-slate-400 text-sm text-left {}",
+                if selected.get() == Some(id) {
+                    "bg-purple-200 dark:bg-purple-900"
+                } else {
+                    ""
+                }
+            )
+        };
+        view! {
+            <li>
+                <button
+                    type="button"
+                    class=class
+                    title=stakeholder.role.clone()
+                    aria-pressed=move || (selected.get() == Some(id)).to_string()
+                    on:click=move |_| {
+                        set_selected.update(|s| *s = if *s == Some(id) { None } else { Some(id) })
+                    }
+                >
+                    {stakeholder.name.clone()}
+                </button>
+            </li>
+        }
+    };
+
+    let cell = move |influence: u8, interest: u8| {
+        let placed = move || {
+            stakeholders
+                .get()
+                .into_iter()
+                .filter(|(_, s)| s.influence == Some(influence) && s.interest == Some(interest))
+                .map(|(id, s)| chip(id, s))
+                .collect_view()
+        };
+        let place_label = move || {
+            t!(
+                "worksheets.problem.map.place_here",
+                name = selected_name.get().unwrap_or_default()
+            )
+            .to_string()
+        };
+        view! {
+            <div class="flex flex-col gap-2 min-h-24 p-2 rounded border border-dashed border-slate-400">
+                {Stakeholder::strategy(influence, interest)
+                    .map(|key| view! { <p class="text-xs opacity-75">{t!(key)}</p> })}
+                <ul class="flex flex-wrap gap-2">{placed}</ul>
+                <Show when=move || selected_name.get().is_some()>
+                    <ButtonView
+                        size=ButtonSize::Sm
+                        attr:aria-label=place_label
+                        attr:title=place_label
+                        on:click=move |e| {
+                            e.prevent_default();
+                            on_place(influence, interest);
+                        }
+                    >
+                        "+"
+                    </ButtonView>
+                </Show>
+            </div>
+        }
+    };
+
+    let unplaced = move || {
+        stakeholders
+            .get()
+            .into_iter()
+            .filter(|(_, s)| !s.is_placed())
+            .map(|(id, s)| chip(id, s))
+            .collect_view()
+    };
+
+    view! {
+        <div id="problem-stakeholder_map" class="text-left">
+            <h4 class="text-xl mb-4 text-center">
+                {t!("worksheets.problem.map.title")}
+            </h4>
+            <div class="max-w-prose mb-4 whitespace-pre-line">
+                <p>{t!("worksheets.problem.map.instruction")}</p>
+            </div>
+            <p class="mb-2">{t!("worksheets.problem.map.unplaced")}</p>
+            <ul class="flex flex-wrap gap-2 mb-4">{unplaced}</ul>
+            <Show when=move || selected_name.get().is_some()>
+                <label class="block mb-4">
+                    <p class="mb-2">
+                        {t!("worksheets.problem.map.label_role")}
+                        ": "
+                        {selected_name}
+                    </p>
+                    <StringInputView
+                        input_type="text"
+                        value=role
+                        placeholder={t!("worksheets.problem.map.placeholder_role").to_string()}
+                    />
+                </label>
+            </Show>
+            <div class="grid grid-cols-[auto_1fr] gap-2">
+                <p class="[writing-mode:vertical-lr] rotate-180 text-center">
+                    {t!("worksheets.problem.map.label_influence")}
+                </p>
+                <div
+                    class="grid gap-2"
+                    style=format!("grid-template-columns: repeat({STAKEHOLDER_LEVELS}, minmax(0, 1fr))")
+                >
+                    {(1..=STAKEHOLDER_LEVELS)
+                        .rev()
+                        .flat_map(|influence| {
+                            (1..=STAKEHOLDER_LEVELS).map(move |interest| cell(influence, interest))
+                        })
+                        .collect_view()}
+                </div>
+                <span></span>
+                <p class="text-center">{t!("worksheets.problem.map.label_interest")}</p>
+            </div>
+        </div>
+    }
+}
@@ -1,10 +1,10 @@
 use leptos::*;
 use leptos_router::*;
-use strum::VariantArray;
 
 use crate::app::{
     components::{ButtonView, IconView, RvArtboardView},
-    state::{use_store, ProcessStep, SeqStep},
+    process::ValidationHintView,
+    state::{use_store, SeqStep, TemplateStep},
     use_lang,
 };
 
@@ -33,6 +33,21 @@ pub fn StepperView() -> impl IntoView {
             .unwrap_or_default()
     });
 
+    // examples are complete, only own worksheets link to missing fields
+    let step_report = Signal::derive(move || {
+        let data = step_data.get();
+        if data.example.is_some() {
+            None
+        } else {
+            state
+                .get()
+                .template()
+                .step(data.path.as_str())
+                .and_then(|step| step.report(&state.get().wk.get()))
+        }
+        .unwrap_or_default()
+    });
+
     let prev_button_disabled = Signal::derive(move || step_idx.get() == 0);
     let prev_button_text = Signal::derive(move || {
         if prev_button_disabled.get() {
@@ -132,25 +147,41 @@ pub fn StepperView() -> impl IntoView {
             </ButtonView>
             <ol class="md:basis-full md:max-xl:mx-auto lg:basis-auto lg:shrink lg:order-2 xl:order-first flex flex-col flex-wrap sm:flex-row xl:flex-col justify-center xl:gap-4 xl:mb-4">
                 <For
-                    each=move || ProcessStep::VARIANTS.into_iter()
-                    key=|state| *state
+                    each=move || state.get().template().steps.clone()
+                    key=|step| step.id.clone()
                     let:child
                 >
                     <li class="contents">
-                        <StepView step=*child current_step_data=step_data/>
+                        <StepView step=child current_step_data=step_data/>
                     </li>
                 </For>
             </ol>
+            <div class="md:basis-full lg:order-last xl:max-w-48">
+                <ValidationHintView report=step_report/>
+            </div>
         </aside>
     }
 }
 
 #[component]
-fn StepView(step: ProcessStep, current_step_data: Signal<SeqStep>) -> impl IntoView {
+fn StepView(step: TemplateStep, current_step_data: Signal<SeqStep>) -> impl IntoView {
     let lang = use_lang();
     let (input, set_input) = create_signal(None);
 
-    let is_inactive = Signal::derive(move || current_step_data.get().process_step != step);
+    let state = use_store();
+    let path = step.path();
+    let is_inactive = {
+        let path = path.clone();
+        Signal::derive(move || current_step_data.get().path != path)
+    };
+
+    let progress = {
+        let step = step.clone();
+        Signal::derive(move || {
+            step.report(&state.get().wk.get())
+                .map(|r| format!("{}/{}", r.passed(), r.checked))
+        })
+    };
 
     let activate_cb = Callback::new(move |hover| {
         let is_inactive = is_inactive.get();
@@ -161,18 +192,16 @@ fn StepView(step: ProcessStep, current_step_data: Signal<SeqStep>) -> impl IntoV
         )));
     });
 
-    let label = Signal::derive(move || {
-        let l_id = format!("stepper.{}", step.to_string().to_lowercase());
-        t!(l_id.as_str()).to_string()
-    });
+    let title = step.title();
+    let label = Signal::derive(move || t!(title.as_str()).to_string());
 
     let href = Signal::derive(move || {
         let lang = lang.get();
         let data = current_step_data.get();
         if let Some(example) = data.example.as_ref() {
-            format!("/{lang}/process/{}/{example}", step as isize)
+            format!("/{lang}/process/{path}/{example}")
         } else {
-            format!("/{lang}/process/{}", step as isize)
+            format!("/{lang}/process/{path}")
         }
     });
 
@@ -191,11 +220,16 @@ fn StepView(step: ProcessStep, current_step_data: Signal<SeqStep>) -> impl IntoV
         >
             <RvArtboardView
                 attr:class="mt-1 w-16 h-16 sm:w-8 sm:h-8 xl:w-12 xl:h-12"
-                state_machine={format!("{step} State Machine")}
-                name={format!("{step}")}
+                state_machine={format!("{} State Machine", step.artboard())}
+                name={step.artboard()}
                 input_bool=input
             />
-            <span class="my-2 xl:ml-4 text-sm block w-full">{label}</span>
+            <span class="my-2 xl:ml-4 text-sm block w-full">
+                {label}
+                <Show when=move || progress.get().is_some()>
+                    <small class="block text-xs opacity-75">{progress}</small>
+                </Show>
+            </span>
         </A>
     }
 }
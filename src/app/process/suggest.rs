@@ -0,0 +1,229 @@
+#![cfg_attr(not(feature = "ssr"), allow(unused))]
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use strum::Display;
+
+use crate::app::{
+    components::{use_wk_state, ButtonSize, ButtonView, IconView, Language, Status, StatusView},
+    state::{keyed_values, WorkSheets},
+    use_lang,
+};
+
+#[cfg(feature = "ssr")]
+use crate::server::safe_error;
+
+/// most suggestions offered at once
+const MAX_SUGGESTIONS: usize = 5;
+
+/// most tokens of the list of suggestions
+const MAX_TOKENS: u32 = 256;
+
+/// list of a worksheet suggestions are made for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+pub enum SuggestTarget {
+    Stakeholders,
+    Solutions,
+    Research,
+    Now,
+    Best,
+}
+
+impl SuggestTarget {
+    /// entries of the target already in the worksheets
+    pub fn entries(&self, wk: &WorkSheets) -> Vec<String> {
+        match self {
+            SuggestTarget::Stakeholders => keyed_values(&wk.problem.stakeholders),
+            SuggestTarget::Solutions => keyed_values(&wk.solutions.solutions),
+            SuggestTarget::Research => wk
+                .compromise
+                .question
+                .lines()
+                .map(|l| l.to_string())
+                .collect(),
+            SuggestTarget::Now => wk.implement.now.clone(),
+            SuggestTarget::Best => wk.implement.best.clone(),
+        }
+    }
+}
+
+/// text of a list item after its `-`, `*`, `•`, `1.` or `1)` marker, none for other lines
+fn list_item(line: &str) -> Option<&str> {
+    let line = line.trim();
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    let rest = if digits > 0 {
+        line[digits..].strip_prefix(['.', ')'])?
+    } else {
+        line.strip_prefix(['-', '*', '•'])?
+    };
+    rest.starts_with(char::is_whitespace).then(|| rest.trim())
+}
+
+/// list items of a response, without markers and the entries already known,
+/// lines without a marker like a preamble are left out
+fn parse_suggestions(response: &str, known: &[String]) -> Vec<String> {
+    let known = known
+        .iter()
+        .map(|k| k.trim().to_lowercase())
+        .collect::<Vec<_>>();
+
+    let mut suggestions: Vec<String> = vec![];
+    for entry in response.lines().filter_map(list_item) {
+        if entry.is_empty()
+            || known.contains(&entry.to_lowercase())
+            || suggestions.iter().any(|s| s.eq_ignore_ascii_case(entry))
+        {
+            continue;
+        }
+        suggestions.push(entry.to_string());
+        if suggestions.len() == MAX_SUGGESTIONS {
+            break;
+        }
+    }
+    suggestions
+}
+
+/// proposes entries for a list of the worksheets
+#[server(SuggestEntries, "/api")]
+pub async fn suggest_entries(
+    wk: WorkSheets,
+    lang: Language,
+    target: SuggestTarget,
+) -> Result<Vec<String>, ServerFnError<String>> {
+    use super::{budget_prompt, filter_output, PromptTemplate};
+    use crate::inference::{inference_backend, InferenceParams};
+
+    println!("suggest entries");
+
+    let known = target.entries(&wk);
+
+    let template = PromptTemplate::load(&lang).map_err(safe_error)?;
+    let (prompt, _) = budget_prompt(wk, MAX_TOKENS, |empty, workbook| {
+        template.render_suggestions(target, empty, workbook)
+    })?;
+
+    let output = inference_backend()
+        .and_then(|backend| {
+            backend.infer(
+                prompt.as_str(),
+                InferenceParams {
+                    max_tokens: MAX_TOKENS,
+                    temperature: 0.7,
+                },
+            )
+        })
+        .map_err(safe_error)?;
+
+    let filtered = filter_output(prompt.as_str(), output.text.as_str());
+    if filtered.flags.iter().any(|f| f == "harm") {
+        return Ok(vec![]);
+    }
+
+    Ok(parse_suggestions(filtered.text.as_str(), &known))
+}
+
+/// suggestions for a list, accepted one by one
+#[component]
+pub fn SuggestView(
+    target: SuggestTarget,
+    #[prop(into)] on_accept: Callback<String>,
+) -> impl IntoView {
+    let state = use_wk_state();
+    let lang = use_lang();
+
+    let suggest_action = create_action(move |data: &(WorkSheets, Language)| {
+        let (wk, lang) = data.clone();
+        async move {
+            suggest_entries(wk, lang, target)
+                .await
+                .map_err(ServerFnErrorErr::from)
+        }
+    });
+    let pending = suggest_action.pending();
+
+    let suggestions = create_rw_signal(Vec::<String>::new());
+    let failed = Signal::derive(move || matches!(suggest_action.value().get(), Some(Err(_))));
+
+    create_effect(move |_| {
+        if let Some(Ok(next)) = suggest_action.value().get() {
+            suggestions.set(next);
+        }
+    });
+
+    let on_suggest = move |e: ev::MouseEvent| {
+        e.prevent_default();
+        let wk: WorkSheets = state.get_untracked().get_untracked();
+        suggest_action.dispatch((wk, lang.get_untracked()));
+    };
+
+    let dismiss = move |value: &str| suggestions.update(|s| s.retain(|v| v != value));
+
+    view! {
+        <div class="mt-2 mb-4 text-left">
+            <div class="flex justify-center">
+                <ButtonView
+                    size=ButtonSize::Sm
+                    disabled=pending
+                    on:click=on_suggest
+                >
+                    {move || if suggestions.get().is_empty() {
+                        t!("worksheets.suggest.cta")
+                    } else {
+                        t!("worksheets.suggest.cta_again")
+                    }}
+                </ButtonView>
+            </div>
+            <Show when=move || pending.get()>
+                <StatusView status=Status::Pending attr:class="my-2 mx-auto"/>
+            </Show>
+            <Show when=move || failed.get()>
+                <p class="text-sm opacity-80 text-center mt-2">{t!("worksheets.suggest.failed")}</p>
+            </Show>
+            <Show when=move || !suggestions.get().is_empty()>
+                <p class="text-sm opacity-80 mt-2 mb-2">{t!("worksheets.suggest.disclaimer")}</p>
+                <ul class="flex flex-col gap-2">
+                    <For
+                        each=move || suggestions.get()
+                        key=|s| s.clone()
+                        let:suggestion
+                    >
+                        {
+                            let accepted = suggestion.clone();
+                            let dismissed = suggestion.clone();
+                            view! {
+                                <li class="grid grid-cols-[1fr_auto_auto] gap-2 items-center p-2 rounded border border-dashed border-slate-400">
+                                    <span class="whitespace-pre-line">{suggestion}</span>
+                                    <ButtonView
+                                        cta=1
+                                        size=ButtonSize::Sm
+                                        attr:title=t!("worksheets.suggest.accept").to_string()
+                                        attr:aria-label=t!("worksheets.suggest.accept").to_string()
+                                        on:click=move |e| {
+                                            e.prevent_default();
+                                            on_accept.call(accepted.clone());
+                                            dismiss(accepted.as_str());
+                                        }
+                                    >
+                                        <IconView icon="Done"/>
+                                    </ButtonView>
+                                    <ButtonView
+                                        cta=-1
+                                        size=ButtonSize::Sm
+                                        attr:title=t!("worksheets.suggest.dismiss").to_string()
+                                        attr:aria-label=t!("worksheets.suggest.dismiss").to_string()
+                                        on:click=move |e| {
+                                            e.prevent_default();
+                                            dismiss(dismissed.as_str());
+                                        }
+                                    >
+                                        <IconView icon="Close"/>
+                                    </ButtonView>
+                                </li>
+                            }
+                        }
+                    </For>
+                </ul>
+            </Show>
+        </div>
+    }
+}
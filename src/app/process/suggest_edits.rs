@@ -0,0 +1,347 @@
+#![cfg_attr(not(feature = "ssr"), allow(unused))]
+
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumString, VariantArray};
+use uuid::Uuid;
+
+use crate::app::{
+    components::{use_wk_state, ButtonSize, ButtonView, IconView, Language, Status, StatusView},
+    state::{
+        keyed_values, ImplementWKFormState, IterateWKFormState, ProblemWKFormState,
+        SolutionsWKFormState, WorkSheets, WorkSheetsFormState,
+    },
+    use_lang,
+};
+
+#[cfg(feature = "ssr")]
+use crate::server::safe_error;
+
+/// most edits offered at once
+const MAX_EDITS: usize = 8;
+
+/// most tokens of the json of the edits
+const MAX_TOKENS: u32 = 768;
+
+/// field of the worksheets an edit applies to, named like in the validation report
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    EnumString,
+    VariantArray,
+    Display,
+)]
+pub enum EditField {
+    #[serde(rename = "problem.problems")]
+    #[strum(to_string = "problem.problems")]
+    Problems,
+    #[serde(rename = "problem.stakeholders")]
+    #[strum(to_string = "problem.stakeholders")]
+    Stakeholders,
+    #[serde(rename = "problem.problem_statement")]
+    #[strum(to_string = "problem.problem_statement")]
+    ProblemStatement,
+    #[serde(rename = "solutions.solutions")]
+    #[strum(to_string = "solutions.solutions")]
+    Solutions,
+    #[serde(rename = "compromise.question")]
+    #[strum(to_string = "compromise.question")]
+    Question,
+    #[serde(rename = "implement.now")]
+    #[strum(to_string = "implement.now")]
+    Now,
+    #[serde(rename = "implement.best")]
+    #[strum(to_string = "implement.best")]
+    Best,
+    #[serde(rename = "iterate.title")]
+    #[strum(to_string = "iterate.title")]
+    Title,
+    #[serde(rename = "iterate.resources")]
+    #[strum(to_string = "iterate.resources")]
+    Resources,
+    #[serde(rename = "iterate.external_resources")]
+    #[strum(to_string = "iterate.external_resources")]
+    ExternalResources,
+}
+
+impl EditField {
+    /// whether the value of an edit is added as an entry instead of replacing the field
+    pub fn is_list(&self) -> bool {
+        !matches!(
+            self,
+            EditField::ProblemStatement | EditField::Question | EditField::Title
+        )
+    }
+
+    /// values of the field in the worksheets
+    pub fn values(&self, wk: &WorkSheets) -> Vec<String> {
+        match self {
+            EditField::Problems => keyed_values(&wk.problem.problems),
+            EditField::Stakeholders => keyed_values(&wk.problem.stakeholders),
+            EditField::ProblemStatement => vec![wk.problem.problem_statement.clone()],
+            EditField::Solutions => keyed_values(&wk.solutions.solutions),
+            EditField::Question => vec![wk.compromise.question.clone()],
+            EditField::Now => wk.implement.now.clone(),
+            EditField::Best => wk.implement.best.clone(),
+            EditField::Title => vec![wk.iterate.title.clone()],
+            EditField::Resources => wk.iterate.resources.clone(),
+            EditField::ExternalResources => wk.iterate.external_resources.clone(),
+        }
+    }
+
+    /// locale key of the field name
+    pub fn label(&self) -> String {
+        format!("worksheets.fields.{}", self.to_string().replace('.', "_"))
+    }
+}
+
+/// edit of one field of the worksheets suggested by the model
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuggestedEdit {
+    pub field: EditField,
+    pub value: String,
+    #[serde(default)]
+    pub reason: String,
+}
+
+impl SuggestedEdit {
+    /// updates the form state of the field
+    pub fn apply(&self, wk: &WorkSheetsFormState) {
+        let value = || self.value.clone();
+        let keyed = || (Uuid::new_v4(), value());
+        match self.field {
+            EditField::Problems => wk.problem.update(|p| {
+                let (id, value) = keyed();
+                p.problems
+                    .push(ProblemWKFormState::problems_entry(id, value))
+            }),
+            EditField::Stakeholders => wk.problem.update(|p| {
+                let (id, value) = keyed();
+                p.stakeholders
+                    .push(ProblemWKFormState::stakeholders_entry(id, value.into()))
+            }),
+            EditField::ProblemStatement => {
+                wk.problem.get_untracked().problem_statement.set(value())
+            }
+            EditField::Solutions => wk.solutions.update(|s| {
+                let (id, value) = keyed();
+                s.solutions
+                    .push(SolutionsWKFormState::solutions_entry(id, value))
+            }),
+            EditField::Question => wk.compromise.get_untracked().question.set(value()),
+            EditField::Now => wk
+                .implement
+                .update(|i| i.now.push(ImplementWKFormState::now_entry(value()))),
+            EditField::Best => wk
+                .implement
+                .update(|i| i.best.push(ImplementWKFormState::best_entry(value()))),
+            EditField::Title => wk.iterate.get_untracked().title.set(value()),
+            EditField::Resources => wk.iterate.update(|i| {
+                i.resources
+                    .push(IterateWKFormState::resources_entry(value()))
+            }),
+            EditField::ExternalResources => wk.iterate.update(|i| {
+                i.external_resources
+                    .push(IterateWKFormState::external_resources_entry(value()))
+            }),
+        }
+    }
+}
+
+/// valid edits of a json response, edits which change nothing are left out
+#[cfg(feature = "ssr")]
+fn parse_edits(response: &str, wk: &WorkSheets) -> Vec<SuggestedEdit> {
+    use super::{escape_text, FIELD_CAP};
+
+    // the model may wrap the array in prose or a code block
+    let json = match (response.find('['), response.rfind(']')) {
+        (Some(start), Some(end)) if start < end => &response[start..=end],
+        _ => return vec![],
+    };
+    let items: Vec<serde_json::Value> = serde_json::from_str(json).unwrap_or_default();
+
+    let mut edits: Vec<SuggestedEdit> = vec![];
+    for item in items {
+        let Ok(edit) = serde_json::from_value::<SuggestedEdit>(item) else {
+            continue;
+        };
+        let value = escape_text(edit.value.trim(), FIELD_CAP);
+        let known = edit.field.values(wk);
+        if value.is_empty()
+            || known
+                .iter()
+                .any(|k| k.trim().eq_ignore_ascii_case(value.as_str()))
+            || edits
+                .iter()
+                .any(|e| e.field == edit.field && e.value.eq_ignore_ascii_case(value.as_str()))
+        {
+            continue;
+        }
+        edits.push(SuggestedEdit {
+            field: edit.field,
+            value,
+            reason: escape_text(edit.reason.trim(), FIELD_CAP),
+        });
+        if edits.len() == MAX_EDITS {
+            break;
+        }
+    }
+    edits
+}
+
+/// proposes edits of the worksheets which can be applied one by one
+#[server(SuggestEdits, "/api")]
+pub async fn suggest_edits(
+    wk: WorkSheets,
+    lang: Language,
+) -> Result<Vec<SuggestedEdit>, ServerFnError<String>> {
+    use super::{budget_prompt, filter_output, PromptTemplate};
+    use crate::inference::{inference_backend, InferenceParams};
+
+    println!("suggest edits");
+
+    let template = PromptTemplate::load(&lang).map_err(safe_error)?;
+    let (prompt, _) = budget_prompt(wk.clone(), MAX_TOKENS, |empty, workbook| {
+        template.render_edits(empty, workbook)
+    })?;
+
+    let output = inference_backend()
+        .and_then(|backend| {
+            backend.infer(
+                prompt.as_str(),
+                InferenceParams {
+                    max_tokens: MAX_TOKENS,
+                    temperature: 0.4,
+                },
+            )
+        })
+        .map_err(safe_error)?;
+
+    let filtered = filter_output(prompt.as_str(), output.text.as_str());
+    if filtered.flags.iter().any(|f| f == "harm") {
+        return Ok(vec![]);
+    }
+
+    Ok(parse_edits(filtered.text.as_str(), &wk))
+}
+
+/// edits suggested for the worksheets, reviewed and applied one by one
+#[component]
+pub fn SuggestedEditsView() -> impl IntoView {
+    let state = use_wk_state();
+    let lang = use_lang();
+
+    let edits_action = create_action(move |data: &(WorkSheets, Language)| {
+        let (wk, lang) = data.clone();
+        async move {
+            suggest_edits(wk, lang)
+                .await
+                .map_err(ServerFnErrorErr::from)
+        }
+    });
+    let pending = edits_action.pending();
+
+    let edits = create_rw_signal(Vec::<SuggestedEdit>::new());
+    let failed = Signal::derive(move || matches!(edits_action.value().get(), Some(Err(_))));
+    let none =
+        Signal::derive(move || matches!(edits_action.value().get(), Some(Ok(e)) if e.is_empty()));
+
+    create_effect(move |_| {
+        if let Some(Ok(next)) = edits_action.value().get() {
+            edits.set(next);
+        }
+    });
+
+    let on_suggest = move |e: ev::MouseEvent| {
+        e.prevent_default();
+        let wk: WorkSheets = state.get_untracked().get_untracked();
+        edits_action.dispatch((wk, lang.get_untracked()));
+    };
+
+    let dismiss = move |edit: &SuggestedEdit| edits.update(|e| e.retain(|v| v != edit));
+
+    view! {
+        <div class="mt-8 max-w-prose mx-auto" id="inquire-edits">
+            <div class="flex justify-center">
+                <ButtonView
+                    size=ButtonSize::Sm
+                    disabled=pending
+                    on:click=on_suggest
+                >
+                    {t!("worksheets.edits.cta")}
+                </ButtonView>
+            </div>
+            <Show when=move || pending.get()>
+                <StatusView status=Status::Pending attr:class="my-2 mx-auto"/>
+            </Show>
+            <Show when=move || failed.get()>
+                <p class="text-sm opacity-80 text-center mt-2">{t!("worksheets.edits.failed")}</p>
+            </Show>
+            <Show when=move || none.get()>
+                <p class="text-sm opacity-80 text-center mt-2">{t!("worksheets.edits.none")}</p>
+            </Show>
+            <Show when=move || !edits.get().is_empty()>
+                <p class="text-sm opacity-80 mt-2 mb-2">{t!("worksheets.suggest.disclaimer")}</p>
+                <ul class="flex flex-col gap-2">
+                    <For
+                        each=move || edits.get()
+                        key=|e| (e.field, e.value.clone())
+                        let:edit
+                    >
+                        {
+                            let applied = edit.clone();
+                            let dismissed = edit.clone();
+                            let action = if edit.field.is_list() {
+                                t!("worksheets.edits.add")
+                            } else {
+                                t!("worksheets.edits.replace")
+                            };
+                            view! {
+                                <li class="grid grid-cols-[1fr_auto_auto] gap-2 items-center p-2 rounded border border-dashed border-slate-400">
+                                    <div>
+                                        <p class="text-sm opacity-80">
+                                            {format!("{} · {action}", t!(edit.field.label().as_str()))}
+                                        </p>
+                                        <p class="whitespace-pre-line">{edit.value}</p>
+                                        <p class="text-sm opacity-80 whitespace-pre-line">{edit.reason}</p>
+                                    </div>
+                                    <ButtonView
+                                        cta=1
+                                        size=ButtonSize::Sm
+                                        attr:title=t!("worksheets.edits.apply").to_string()
+                                        attr:aria-label=t!("worksheets.edits.apply").to_string()
+                                        on:click=move |e| {
+                                            e.prevent_default();
+                                            applied.apply(&state.get_untracked());
+                                            dismiss(&applied);
+                                        }
+                                    >
+                                        <IconView icon="Done"/>
+                                    </ButtonView>
+                                    <ButtonView
+                                        cta=-1
+                                        size=ButtonSize::Sm
+                                        attr:title=t!("worksheets.suggest.dismiss").to_string()
+                                        attr:aria-label=t!("worksheets.suggest.dismiss").to_string()
+                                        on:click=move |e| {
+                                            e.prevent_default();
+                                            dismiss(&dismissed);
+                                        }
+                                    >
+                                        <IconView icon="Close"/>
+                                    </ButtonView>
+                                </li>
+                            }
+                        }
+                    </For>
+                </ul>
+            </Show>
+        </div>
+    }
+}
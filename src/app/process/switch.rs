@@ -4,19 +4,22 @@ use leptos_router::*;
 use crate::app::{
     components::{use_wk_ctx, ExampleView},
     process::*,
+    state::{use_store, ProcessStep},
 };
 
 #[component]
 pub fn ProcessSwitchView() -> impl IntoView {
     let params = use_params_map();
     let wk_ctx = use_wk_ctx();
+    let store = use_store();
+    let template = create_memo(move |_| store.get().template());
 
     let view = move || {
         let p = params.get();
-        let step: usize = p
-            .get("step")
-            .map(|s| s.parse().ok())
-            .flatten()
+        let step = template
+            .get()
+            .step(p.get("step").cloned().unwrap_or_default().as_str())
+            .cloned()
             .unwrap_or_default();
         let example = p.get("example");
 
@@ -35,25 +38,27 @@ pub fn ProcessSwitchView() -> impl IntoView {
                 view! {
                     <ExampleView>
                         {
-                            match step {
-                                1 => ExampleProblemView.into_view(),
-                                2 => ExampleSolutionView.into_view(),
-                                3 => ExampleCompromiseView.into_view(),
-                                4 => ExampleImplementView.into_view(),
+                            match step.kind {
+                                ProcessStep::Problem => ExampleProblemView.into_view(),
+                                ProcessStep::Solution => ExampleSolutionView.into_view(),
+                                ProcessStep::Compromise => ExampleCompromiseView.into_view(),
+                                ProcessStep::Implement => ExampleImplementView.into_view(),
                                 _ => AboutView.into_view()
                             }
                         }
                     </ExampleView>
                 }
             }
-            None => match step {
-                1 => ProblemView.into_view(),
-                2 => SolutionView.into_view(),
-                3 => CompromiseView.into_view(),
-                4 => ImplementView.into_view(),
-                5 => IterateView.into_view(),
-                6 => InquireView.into_view(),
-                _ => AboutView.into_view(),
+            None => match step.kind {
+                ProcessStep::About => AboutView.into_view(),
+                ProcessStep::Problem => ProblemView.into_view(),
+                ProcessStep::Solution => SolutionView.into_view(),
+                ProcessStep::Compromise => CompromiseView.into_view(),
+                ProcessStep::Implement => ImplementView.into_view(),
+                ProcessStep::Iterate => IterateView.into_view(),
+                ProcessStep::Inquire => InquireView.into_view(),
+                ProcessStep::Reflect => ReflectView.into_view(),
+                ProcessStep::Custom => view! { <CustomStepView step/> }.into_view(),
             },
         }
     };
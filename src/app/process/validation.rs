@@ -0,0 +1,34 @@
+use leptos::*;
+use leptos_router::*;
+
+use crate::app::{state::ValidationReport, use_lang};
+
+/// explains why a call to action is disabled, each issue links to its field
+#[component]
+pub fn ValidationHintView(#[prop(into)] report: Signal<ValidationReport>) -> impl IntoView {
+    let lang = use_lang();
+
+    view! {
+        <Show when=move || !report.get().is_valid()>
+            <div class="max-w-prose mx-auto mt-4 text-sm">
+                <p class="mb-1">{t!("worksheets.validation.title")}</p>
+                <ul class="list-disc pl-6">
+                    <For
+                        each=move || report.get().issues
+                        key=|issue| issue.field.clone()
+                        let:issue
+                    >
+                        <li>
+                            <A
+                                href=format!("/{}/process/{}#{}", lang.get_untracked(), issue.path, issue.anchor())
+                                class="underline hover:text-purple-800 active:text-purple-950"
+                            >
+                                {issue.message()}
+                            </A>
+                        </li>
+                    </For>
+                </ul>
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,156 @@
+use chrono::{NaiveDate, Utc};
+use leptos::*;
+use uuid::Uuid;
+
+use crate::app::{
+    components::{use_wk_state, ButtonView, IconView},
+    state::WorkSheets,
+    util::data_url,
+};
+
+use super::export_file_name;
+
+const ICS_MIME: &str = "text/calendar;charset=utf-8";
+
+/// renders the iteration and its milestones as an iCalendar file
+///
+/// events span whole days, none are rendered without dates
+pub fn worksheets_calendar(wk: &WorkSheets) -> Option<String> {
+    let iterate = &wk.iterate;
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+    let mut events = vec![];
+
+    if let (Some(start), Some(end)) = (iterate.start_date, iterate.end_date) {
+        // the iteration keeps its uid as long as its title and start stay the same
+        let uid = Uuid::new_v5(
+            &Uuid::NAMESPACE_OID,
+            format!("{}{start}", iterate.title).as_bytes(),
+        );
+        events.push(ics_event(
+            uid,
+            stamp.as_str(),
+            iterate.title.as_str(),
+            wk.problem.problem_statement.as_str(),
+            start,
+            end,
+        ));
+    }
+
+    for milestone in iterate.milestones.iter().filter(|m| !m.title.is_empty()) {
+        if let Some(date) = milestone.date {
+            events.push(ics_event(
+                milestone.id,
+                stamp.as_str(),
+                format!("{}: {}", iterate.title, milestone.title).as_str(),
+                "",
+                date,
+                date,
+            ));
+        }
+    }
+
+    if events.is_empty() {
+        return None;
+    }
+
+    let lines = [
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:-//{}//{}//EN", t!("name"), t!("about.title")),
+        "CALSCALE:GREGORIAN".to_string(),
+    ]
+    .into_iter()
+    .chain(events.into_iter().flatten())
+    .chain(["END:VCALENDAR".to_string()]);
+
+    Some(lines.map(|l| ics_fold(l.as_str())).collect::<String>())
+}
+
+/// all day event, the end date is inclusive
+fn ics_event(
+    uid: Uuid,
+    stamp: &str,
+    summary: &str,
+    description: &str,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Vec<String> {
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:{uid}"),
+        format!("DTSTAMP:{stamp}"),
+        format!("DTSTART;VALUE=DATE:{}", start.format("%Y%m%d")),
+        format!(
+            "DTEND;VALUE=DATE:{}",
+            end.succ_opt().unwrap_or(end).format("%Y%m%d")
+        ),
+        format!("SUMMARY:{}", ics_escape(summary)),
+    ];
+    if !description.is_empty() {
+        lines.push(format!("DESCRIPTION:{}", ics_escape(description)));
+    }
+    lines.push("END:VEVENT".to_string());
+    lines
+}
+
+fn ics_escape(value: &str) -> String {
+    value
+        .trim()
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// folds a content line into lines of at most 75 octets ending with CRLF
+fn ics_fold(line: &str) -> String {
+    let mut folded = String::default();
+    let mut len = 0;
+    for c in line.chars() {
+        if len + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            len = 1;
+        }
+        folded.push(c);
+        len += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+#[component]
+pub fn CalendarExportView() -> impl IntoView {
+    let state = use_wk_state();
+
+    let calendar = Signal::derive(move || {
+        let wk: WorkSheets = state.get().get();
+        worksheets_calendar(&wk)
+    });
+
+    let href = Signal::derive(move || {
+        calendar
+            .get()
+            .map(|ics| data_url(ICS_MIME, ics.as_bytes()))
+            .unwrap_or_default()
+    });
+
+    let file_name = Signal::derive(move || {
+        let wk: WorkSheets = state.get().get();
+        format!("{}.ics", export_file_name(&wk))
+    });
+
+    view! {
+        <Show when=move || calendar.get().is_some()>
+            <ButtonView
+                cta=1
+                link=href
+                attr:download=file_name
+            >
+                <IconView icon="Download"/>
+                {t!("worksheets.download.calendar")}
+            </ButtonView>
+        </Show>
+    }
+}
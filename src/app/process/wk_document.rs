@@ -0,0 +1,368 @@
+use std::fmt::Write;
+
+use leptos::*;
+
+use crate::app::{
+    components::{use_wk_state, ButtonView, IconView},
+    state::{format_date, WorkSheets},
+    use_lang,
+    util::download_file,
+    Language,
+};
+
+use super::export_file_name;
+
+const ODT_MIME: &str = "application/vnd.oasis.opendocument.text";
+
+/// renders the workbook into an OpenDocument Text file
+///
+/// the document mirrors sections of the printable summary,
+/// iteration metadata is stored in the document properties
+pub fn worksheets_odt(wk: &WorkSheets, lang: &Language) -> Vec<u8> {
+    let manifest = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0" manifest:version="1.2">
+    <manifest:file-entry manifest:full-path="/" manifest:version="1.2" manifest:media-type="{ODT_MIME}"/>
+    <manifest:file-entry manifest:full-path="content.xml" manifest:media-type="text/xml"/>
+    <manifest:file-entry manifest:full-path="styles.xml" manifest:media-type="text/xml"/>
+    <manifest:file-entry manifest:full-path="meta.xml" manifest:media-type="text/xml"/>
+</manifest:manifest>"#
+    );
+
+    let content = odt_content(wk);
+    let meta = odt_meta(wk, lang);
+
+    // mimetype must be the first entry and stored uncompressed
+    zip_store(&[
+        ("mimetype", ODT_MIME.as_bytes()),
+        ("META-INF/manifest.xml", manifest.as_bytes()),
+        ("meta.xml", meta.as_bytes()),
+        ("styles.xml", odt_styles().as_bytes()),
+        ("content.xml", content.as_bytes()),
+    ])
+}
+
+/// paragraph styles of the headings used by [odt_heading]
+fn odt_styles() -> String {
+    let headings = [(1, "130%"), (2, "115%"), (3, "101%")]
+        .into_iter()
+        .fold(String::default(), |mut acc, (level, size)| {
+            _ = write!(
+                acc,
+                r#"<style:style style:name="Heading_20_{level}" style:display-name="Heading {level}" style:family="paragraph" style:parent-style-name="Heading" style:default-outline-level="{level}" style:class="text"><style:text-properties fo:font-size="{size}" fo:font-weight="bold"/></style:style>"#
+            );
+            acc
+        });
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-styles xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0" xmlns:fo="urn:oasis:names:tc:opendocument:xmlns:xsl-fo-compatible:1.0" office:version="1.2">
+    <office:styles>
+        <style:style style:name="Standard" style:family="paragraph" style:class="text"/>
+        <style:style style:name="Heading" style:family="paragraph" style:parent-style-name="Standard" style:class="text"><style:paragraph-properties fo:margin-top="0.42cm" fo:margin-bottom="0.21cm" fo:keep-with-next="always"/></style:style>
+        {headings}
+    </office:styles>
+</office:document-styles>"#
+    )
+}
+
+fn odt_meta(wk: &WorkSheets, lang: &Language) -> String {
+    let user_defined = [
+        (t!("worksheets.download.iteration"), &wk.iterate.title),
+        (
+            t!("worksheets.iterate.label_date_1"),
+            &format_date(wk.iterate.start_date),
+        ),
+        (
+            t!("worksheets.iterate.label_date_2"),
+            &format_date(wk.iterate.end_date),
+        ),
+    ]
+    .into_iter()
+    .fold(String::default(), |acc, (name, value)| {
+        format!(
+            r#"{acc}<meta:user-defined meta:name="{}" meta:value-type="string">{}</meta:user-defined>"#,
+            xml_escape(name.as_ref()),
+            xml_escape(value)
+        )
+    });
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-meta xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:meta="urn:oasis:names:tc:opendocument:xmlns:meta:1.0" xmlns:dc="http://purl.org/dc/elements/1.1/" office:version="1.2">
+    <office:meta>
+        <meta:generator>a.nvlkv.xyz</meta:generator>
+        <dc:title>{}</dc:title>
+        <dc:subject>{}</dc:subject>
+        <dc:description>{}</dc:description>
+        <dc:creator>{}</dc:creator>
+        <dc:language>{lang}</dc:language>
+        <meta:creation-date>{}</meta:creation-date>
+        {user_defined}
+    </office:meta>
+</office:document-meta>"#,
+        xml_escape(wk.iterate.title.as_str()),
+        xml_escape(t!("about.title").as_ref()),
+        xml_escape(wk.problem.problem_statement.as_str()),
+        xml_escape(t!("worksheets.download.signature").as_ref()),
+        chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S"),
+    )
+}
+
+fn odt_content(wk: &WorkSheets) -> String {
+    let mut body = String::default();
+
+    odt_heading(&mut body, 1, t!("about.title").as_ref());
+    odt_paragraph(&mut body, t!("worksheets.download.signature").as_ref());
+
+    odt_heading(
+        &mut body,
+        2,
+        format!(
+            "{}: {}",
+            t!("worksheets.download.iteration"),
+            wk.iterate.title
+        )
+        .as_str(),
+    );
+    odt_paragraph(
+        &mut body,
+        format!(
+            "{}: {}",
+            t!("worksheets.iterate.label_date_1"),
+            format_date(wk.iterate.start_date)
+        )
+        .as_str(),
+    );
+    odt_paragraph(
+        &mut body,
+        format!(
+            "{}: {}",
+            t!("worksheets.iterate.label_date_2"),
+            format_date(wk.iterate.end_date)
+        )
+        .as_str(),
+    );
+
+    odt_heading(&mut body, 2, t!("worksheets.download.problem").as_ref());
+    odt_paragraph(&mut body, wk.problem.problem_statement.as_str());
+
+    odt_heading(&mut body, 2, t!("worksheets.download.compromise").as_ref());
+    odt_heading(
+        &mut body,
+        3,
+        t!("worksheets.compromise.label_solutions").as_ref(),
+    );
+    odt_list(&mut body, &wk.solution_choices());
+    odt_heading(
+        &mut body,
+        3,
+        t!("worksheets.compromise.label_stakeholders").as_ref(),
+    );
+    odt_list(&mut body, &wk.stakeholder_choices());
+    odt_heading(&mut body, 3, t!("worksheets.download.research").as_ref());
+    odt_paragraph(&mut body, wk.compromise.question.as_str());
+
+    odt_heading(&mut body, 2, t!("worksheets.implement.title").as_ref());
+    odt_heading(
+        &mut body,
+        3,
+        format!(
+            "{} – {}",
+            t!("worksheets.implement.label_now"),
+            t!("worksheets.implement.hint_now")
+        )
+        .as_str(),
+    );
+    odt_list(&mut body, &wk.implement.now);
+    odt_heading(
+        &mut body,
+        3,
+        format!(
+            "{} – {}",
+            t!("worksheets.implement.label_best"),
+            t!("worksheets.implement.hint_best")
+        )
+        .as_str(),
+    );
+    odt_list(&mut body, &wk.implement.best);
+    odt_heading(
+        &mut body,
+        3,
+        t!("worksheets.iterate.label_resources").as_ref(),
+    );
+    odt_list(&mut body, &wk.iterate.resources);
+    odt_heading(
+        &mut body,
+        3,
+        t!("worksheets.iterate.label_externals").as_ref(),
+    );
+    odt_list(&mut body, &wk.iterate.external_resources);
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" office:version="1.2">
+    <office:body>
+        <office:text>
+            {body}
+        </office:text>
+    </office:body>
+</office:document-content>"#
+    )
+}
+
+fn odt_heading(body: &mut String, level: usize, text: &str) {
+    _ = write!(
+        body,
+        r#"<text:h text:style-name="Heading_20_{level}" text:outline-level="{level}">{}</text:h>"#,
+        xml_escape(text)
+    );
+}
+
+fn odt_paragraph(body: &mut String, text: &str) {
+    let text = if text.is_empty() {
+        t!("util.empty").to_string()
+    } else {
+        text.to_string()
+    };
+    // preserve line breaks of textarea inputs
+    let lines = text
+        .trim()
+        .lines()
+        .map(xml_escape)
+        .collect::<Vec<_>>()
+        .join("<text:line-break/>");
+    _ = write!(body, "<text:p>{lines}</text:p>");
+}
+
+fn odt_list(body: &mut String, entries: &[String]) {
+    let entries = entries.iter().filter(|e| !e.is_empty()).collect::<Vec<_>>();
+    if entries.is_empty() {
+        odt_paragraph(body, "");
+        return;
+    }
+    _ = write!(body, "<text:list>");
+    for entry in entries {
+        _ = write!(body, "<text:list-item>");
+        odt_paragraph(body, entry.as_str());
+        _ = write!(body, "</text:list-item>");
+    }
+    _ = write!(body, "</text:list>");
+}
+
+/// escapes markup and drops control characters, other than tabs and line breaks they are invalid xml
+fn xml_escape(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| !c.is_control() || matches!(c, '\t' | '\n' | '\r'))
+        .collect::<String>()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// writes an uncompressed zip archive
+fn zip_store(entries: &[(&str, &[u8])]) -> Vec<u8> {
+    // 1980-01-01 00:00 in dos format
+    const DOS_DATE: u16 = 0x21;
+    const VERSION: u16 = 20;
+
+    let mut out = Vec::new();
+    let mut central = Vec::new();
+
+    for (name, data) in entries {
+        let offset = out.len() as u32;
+        let crc = crc32(data);
+        let size = data.len() as u32;
+        let name_len = name.len() as u16;
+
+        out.extend(0x04034b50_u32.to_le_bytes());
+        out.extend(VERSION.to_le_bytes());
+        out.extend(0_u16.to_le_bytes()); // flags
+        out.extend(0_u16.to_le_bytes()); // method: store
+        out.extend(0_u16.to_le_bytes()); // time
+        out.extend(DOS_DATE.to_le_bytes());
+        out.extend(crc.to_le_bytes());
+        out.extend(size.to_le_bytes());
+        out.extend(size.to_le_bytes());
+        out.extend(name_len.to_le_bytes());
+        out.extend(0_u16.to_le_bytes()); // extra
+        out.extend(name.as_bytes());
+        out.extend(data.iter());
+
+        central.extend(0x02014b50_u32.to_le_bytes());
+        central.extend(VERSION.to_le_bytes()); // made by
+        central.extend(VERSION.to_le_bytes()); // needed
+        central.extend(0_u16.to_le_bytes()); // flags
+        central.extend(0_u16.to_le_bytes()); // method: store
+        central.extend(0_u16.to_le_bytes()); // time
+        central.extend(DOS_DATE.to_le_bytes());
+        central.extend(crc.to_le_bytes());
+        central.extend(size.to_le_bytes());
+        central.extend(size.to_le_bytes());
+        central.extend(name_len.to_le_bytes());
+        central.extend(0_u16.to_le_bytes()); // extra
+        central.extend(0_u16.to_le_bytes()); // comment
+        central.extend(0_u16.to_le_bytes()); // disk
+        central.extend(0_u16.to_le_bytes()); // internal attributes
+        central.extend(0_u32.to_le_bytes()); // external attributes
+        central.extend(offset.to_le_bytes());
+        central.extend(name.as_bytes());
+    }
+
+    let central_offset = out.len() as u32;
+    let central_size = central.len() as u32;
+    let count = entries.len() as u16;
+
+    out.extend(central);
+    out.extend(0x06054b50_u32.to_le_bytes());
+    out.extend(0_u16.to_le_bytes()); // disk
+    out.extend(0_u16.to_le_bytes()); // central directory disk
+    out.extend(count.to_le_bytes());
+    out.extend(count.to_le_bytes());
+    out.extend(central_size.to_le_bytes());
+    out.extend(central_offset.to_le_bytes());
+    out.extend(0_u16.to_le_bytes()); // comment
+
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFFFFFF_u32;
+    for b in data {
+        crc ^= *b as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB88320 & mask);
+        }
+    }
+    !crc
+}
+
+#[component]
+pub fn DocumentExportView() -> impl IntoView {
+    let state = use_wk_state();
+    let lang = use_lang();
+
+    // built when clicked rather than on every edit
+    let on_download = move |_| {
+        let wk: WorkSheets = state.get_untracked().get_untracked();
+        download_file(
+            format!("{}.odt", export_file_name(&wk)).as_str(),
+            ODT_MIME,
+            worksheets_odt(&wk, &lang.get_untracked()).as_slice(),
+        );
+    };
+
+    view! {
+        <ButtonView
+            cta=1
+            on:click=on_download
+        >
+            <IconView icon="Download"/>
+            {t!("worksheets.download.document")}
+        </ButtonView>
+    }
+}
@@ -5,10 +5,12 @@ use leptos_meta::*;
 
 use crate::app::{
     components::{use_wk_state, ButtonView, Localized, RvArtboardView, WorksheetView},
-    state::{use_store, StorageMode, WorkSheets},
+    state::{format_date, use_store, StorageMode, WorkSheets},
     tracking::{complete_wk_download, SessionId},
 };
 
+use super::export_file_name;
+
 #[component]
 pub fn WorksheetsDownload() -> impl IntoView {
     let store = use_store();
@@ -49,12 +51,13 @@ fn PrintView() -> impl IntoView {
         wk
     });
     let problem_statement = move || data.get().problem.problem_statement;
-    let solutions = move || data.get().compromise.solution_choices;
-    let stakeholders = move || data.get().compromise.stakeholder_choices;
+    let solutions = move || data.get().solution_choices();
+    let stakeholders = move || data.get().stakeholder_choices();
     let question = move || data.get().compromise.question;
     let iteration_title = move || data.get().iterate.title;
-    let iteration_start_date = move || data.get().iterate.start_date;
-    let iteration_end_date = move || data.get().iterate.end_date;
+    let file_name = move || export_file_name(&data.get());
+    let iteration_start_date = move || format_date(data.get().iterate.start_date);
+    let iteration_end_date = move || format_date(data.get().iterate.end_date);
     let iteration_resources = move || data.get().iterate.resources;
     let iteration_external_resources = move || data.get().iterate.external_resources;
     let now = move || data.get().implement.now;
@@ -97,7 +100,7 @@ fn PrintView() -> impl IntoView {
     };
 
     view! {
-        <Title text={move || format!("{}_{}_{}", iteration_title(), t!("about.title"), t!("name")).replace(&['.', '|', '/', '\\', '>', '<', '!', '?', '*'], "-")}/>
+        <Title text=file_name/>
         <ButtonView
             cta=2
             on:click={on_print}
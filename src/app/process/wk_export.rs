@@ -0,0 +1,338 @@
+use std::{fmt::Write, time::Duration};
+
+use leptos::*;
+
+use crate::app::{
+    components::{use_wk_state, ButtonView, IconView},
+    state::{
+        format_date, keyed_values, FieldKind, ProcessStep, ProcessTemplate, ReflectWK, WorkSheets,
+    },
+    util::{copy_to_clipboard, download_file},
+};
+
+/// file name for exported worksheets without extension
+pub fn export_file_name(wk: &WorkSheets) -> String {
+    format!("{}_{}_{}", wk.iterate.title, t!("about.title"), t!("name"))
+        .replace(['.', '|', '/', '\\', '>', '<', '!', '?', '*'], "-")
+}
+
+/// renders the whole workbook as markdown using localized headings
+pub fn worksheets_markdown(wk: &WorkSheets) -> String {
+    let mut md = String::default();
+
+    _ = writeln!(md, "# {}\n", t!("about.title"));
+
+    _ = writeln!(
+        md,
+        "## {}: {}\n",
+        t!("worksheets.download.iteration"),
+        md_escape(wk.iterate.title.as_str())
+    );
+    _ = writeln!(
+        md,
+        "- {}: {}",
+        t!("worksheets.iterate.label_date_1"),
+        format_date(wk.iterate.start_date)
+    );
+    _ = writeln!(
+        md,
+        "- {}: {}\n",
+        t!("worksheets.iterate.label_date_2"),
+        format_date(wk.iterate.end_date)
+    );
+    if !wk.iterate.milestones.is_empty() {
+        _ = writeln!(md, "### {}\n", t!("worksheets.iterate.label_milestones"));
+        for milestone in wk.iterate.milestones.iter() {
+            _ = writeln!(
+                md,
+                "- {}: {}",
+                format_date(milestone.date),
+                md_escape(milestone.title.trim())
+            );
+        }
+        _ = writeln!(md);
+    }
+
+    _ = writeln!(md, "## {}\n", t!("worksheets.problem.title"));
+    md_list(
+        &mut md,
+        t!("worksheets.problem.label_problems").as_ref(),
+        &keyed_values(&wk.problem.problems),
+    );
+    for (name, problems) in wk.problem.themes() {
+        md_list(
+            &mut md,
+            format!(
+                "{}: {}",
+                t!("worksheets.problem.affinity.label_cluster"),
+                md_escape(name.as_str())
+            )
+            .as_str(),
+            &problems,
+        );
+    }
+    md_list(
+        &mut md,
+        t!("worksheets.compromise.label_stakeholders").as_ref(),
+        &keyed_values(&wk.problem.stakeholders),
+    );
+    md_paragraph(
+        &mut md,
+        t!("worksheets.problem.label_statement").as_ref(),
+        &wk.problem.problem_statement,
+    );
+
+    _ = writeln!(md, "## {}\n", t!("worksheets.solutions.title"));
+    md_list(
+        &mut md,
+        t!("worksheets.solutions.label_solutions").as_ref(),
+        &keyed_values(&wk.solutions.solutions),
+    );
+
+    _ = writeln!(md, "## {}\n", t!("worksheets.compromise.title"));
+    md_list(
+        &mut md,
+        t!("worksheets.compromise.label_solutions").as_ref(),
+        &wk.solution_choices(),
+    );
+    md_list(
+        &mut md,
+        t!("worksheets.compromise.label_stakeholders").as_ref(),
+        &wk.stakeholder_choices(),
+    );
+    md_paragraph(
+        &mut md,
+        t!("worksheets.compromise.label_question").as_ref(),
+        &wk.compromise.question,
+    );
+
+    _ = writeln!(md, "## {}\n", t!("worksheets.implement.title"));
+    md_list(
+        &mut md,
+        format!(
+            "{} – {}",
+            t!("worksheets.implement.label_now"),
+            t!("worksheets.implement.hint_now")
+        )
+        .as_str(),
+        &wk.implement.now,
+    );
+    md_list(
+        &mut md,
+        format!(
+            "{} – {}",
+            t!("worksheets.implement.label_best"),
+            t!("worksheets.implement.hint_best")
+        )
+        .as_str(),
+        &wk.implement.best,
+    );
+
+    _ = writeln!(md, "## {}\n", t!("worksheets.iterate.title"));
+    md_list(
+        &mut md,
+        t!("worksheets.iterate.label_resources").as_ref(),
+        &wk.iterate.resources,
+    );
+    md_list(
+        &mut md,
+        t!("worksheets.iterate.label_externals").as_ref(),
+        &wk.iterate.external_resources,
+    );
+
+    let template = ProcessTemplate::by_id(wk.template.as_str());
+    if template
+        .steps
+        .iter()
+        .any(|s| s.kind == ProcessStep::Reflect)
+    {
+        _ = writeln!(md, "## {}\n", t!("worksheets.reflect.title"));
+        md_reflect(&mut md, &wk.reflect, "###");
+    }
+
+    for step in template
+        .steps
+        .iter()
+        .filter(|s| s.kind == ProcessStep::Custom)
+    {
+        _ = writeln!(md, "## {}\n", t!(step.title().as_str()));
+        for (field, answer) in step.answers(wk) {
+            let label = t!(field.label.as_str());
+            match field.kind {
+                FieldKind::List => md_list(&mut md, label.as_ref(), &answer.values),
+                FieldKind::Text | FieldKind::Textarea => md_paragraph(
+                    &mut md,
+                    label.as_ref(),
+                    answer
+                        .values
+                        .first()
+                        .map(|v| v.as_str())
+                        .unwrap_or_default(),
+                ),
+            }
+        }
+    }
+
+    if !wk.iterations.is_empty() {
+        _ = writeln!(md, "## {}\n", t!("worksheets.reflect.label_iterations"));
+        for record in wk.iterations.iter() {
+            _ = writeln!(
+                md,
+                "### {}\n\n{} – {}\n",
+                md_escape(record.iterate.title.as_str()),
+                format_date(record.iterate.start_date),
+                format_date(record.iterate.end_date)
+            );
+            if !record.problem.problem_statement.is_empty() {
+                _ = writeln!(
+                    md,
+                    "{}\n",
+                    md_escape(record.problem.problem_statement.trim())
+                );
+            }
+            md_reflect(&mut md, &record.reflect, "####");
+        }
+    }
+
+    _ = write!(
+        md,
+        "---\n\n{} – <https://a.nvlkv.xyz>\n",
+        t!("worksheets.download.signature")
+    );
+
+    md
+}
+
+/// outcomes, learnings and the review of the statement under `heading`s
+fn md_reflect(md: &mut String, reflect: &ReflectWK, heading: &str) {
+    for (label, entries) in [
+        (t!("worksheets.reflect.label_outcomes"), &reflect.outcomes),
+        (t!("worksheets.reflect.label_learnings"), &reflect.learnings),
+    ] {
+        _ = writeln!(md, "{heading} {label}\n");
+        md_entries(md, entries);
+    }
+    if !reflect.statement_review.is_empty() {
+        let review = format!("worksheets.reflect.review_{}", reflect.statement_review);
+        _ = writeln!(
+            md,
+            "{heading} {}\n\n{}\n",
+            t!("worksheets.reflect.instruction_review"),
+            t!(review.as_str())
+        );
+    }
+    if !reflect.revised_statement.is_empty() {
+        _ = writeln!(
+            md,
+            "{heading} {}\n\n{}\n",
+            t!("worksheets.reflect.label_revised_statement"),
+            md_escape(reflect.revised_statement.trim())
+        );
+    }
+}
+
+fn md_list(md: &mut String, label: &str, entries: &[String]) {
+    _ = writeln!(md, "### {label}\n");
+    md_entries(md, entries);
+}
+
+fn md_entries(md: &mut String, entries: &[String]) {
+    let entries = entries.iter().filter(|e| !e.is_empty()).collect::<Vec<_>>();
+    if entries.is_empty() {
+        _ = writeln!(md, "_{}_", t!("util.empty"));
+    }
+    for entry in entries {
+        // keep multiline entries inside of the list item
+        _ = writeln!(md, "- {}", md_escape(entry.trim()).replace('\n', "\n  "));
+    }
+    _ = writeln!(md);
+}
+
+fn md_paragraph(md: &mut String, label: &str, value: &str) {
+    _ = writeln!(md, "### {label}\n");
+    if value.is_empty() {
+        _ = writeln!(md, "_{}_\n", t!("util.empty"));
+    } else {
+        _ = writeln!(md, "{}\n", md_escape(value.trim()));
+    }
+}
+
+/// text of the user which renders as typed, e.g. `*` or a leading `#` are not markup
+fn md_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for (i, line) in text.lines().enumerate() {
+        if i > 0 {
+            escaped.push('\n');
+        }
+        let content = line.trim_start();
+        escaped.push_str(&line[..line.len() - content.len()]);
+        // block markers only count at the start of a line
+        let digits = content.chars().take_while(|c| c.is_ascii_digit()).count();
+        let mut rest = content;
+        if content.starts_with(['#', '-', '+', '=']) {
+            escaped.push('\\');
+        } else if digits > 0 && content[digits..].starts_with(['.', ')']) {
+            escaped.push_str(&content[..digits]);
+            escaped.push('\\');
+            rest = &content[digits..];
+        }
+        for c in rest.chars() {
+            if "\\`*_[]<>|~".contains(c) {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+    }
+    escaped
+}
+
+#[component]
+pub fn MarkdownExportView(#[prop(attrs)] attrs: Vec<(&'static str, Attribute)>) -> impl IntoView {
+    let state = use_wk_state();
+    let (copied, set_copied) = create_signal(false);
+
+    // built when clicked rather than on every edit
+    let markdown = move || {
+        let wk: WorkSheets = state.get_untracked().get_untracked();
+        (export_file_name(&wk), worksheets_markdown(&wk))
+    };
+
+    let on_download = move |_| {
+        let (file_name, markdown) = markdown();
+        download_file(
+            format!("{file_name}.md").as_str(),
+            "text/markdown;charset=utf-8",
+            markdown.as_bytes(),
+        );
+    };
+
+    let on_copy = move |_| {
+        copy_to_clipboard(markdown().1.as_str());
+        set_copied.set(true);
+        set_timeout(move || set_copied.set(false), Duration::from_secs(3));
+    };
+
+    html::div()
+        .attrs(attrs)
+        .classes("flex flex-wrap gap-4 justify-center")
+        .child(view! {
+            <ButtonView
+                cta=1
+                on:click=on_download
+            >
+                <IconView icon="Download"/>
+                {t!("worksheets.download.markdown")}
+            </ButtonView>
+            <ButtonView
+                cta=1
+                on:click=on_copy
+            >
+                <IconView icon="Worksheet"/>
+                {move || if copied.get() {
+                    t!("worksheets.download.copied").to_string()
+                } else {
+                    t!("worksheets.download.copy_markdown").to_string()
+                }}
+            </ButtonView>
+        })
+}
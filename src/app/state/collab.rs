@@ -0,0 +1,421 @@
+use std::collections::HashMap;
+
+use form_signal::FormState;
+use leptos::*;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{
+    ImplementWKFormState, IterateWKFormState, ProblemWKFormState, ReflectWKFormState,
+    SolutionsWKFormState, Stakeholder, WorkSheetsFormState,
+};
+
+/// a single synced value of a collaborative session
+///
+/// list items are keyed by their `FormState` id,
+/// single values use the nil id
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollabEntry {
+    pub field: String,
+    pub id: Uuid,
+    pub position: u32,
+    pub value: String,
+    pub deleted: bool,
+    pub revision: i64,
+}
+
+pub type CollabKey = (String, Uuid);
+
+impl CollabEntry {
+    pub fn key(&self) -> CollabKey {
+        (self.field.clone(), self.id)
+    }
+
+    fn same_content(&self, other: &Self) -> bool {
+        self.position == other.position
+            && self.value == other.value
+            && self.deleted == other.deleted
+    }
+}
+
+/// local state of a collaborative session
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CollabSession {
+    pub code: String,
+    pub revision: i64,
+    /// joining participants take over the session state before pushing anything
+    pub joining: bool,
+    pub synced: HashMap<CollabKey, CollabEntry>,
+}
+
+impl CollabSession {
+    pub fn new(code: String, joining: bool) -> Self {
+        Self {
+            code,
+            joining,
+            ..Default::default()
+        }
+    }
+
+    /// local entries which differ from the last synced state
+    pub fn changes(&self, wk: &WorkSheetsFormState) -> Vec<CollabEntry> {
+        if self.joining {
+            return vec![];
+        }
+
+        let local = collab_entries(wk);
+        let mut changes = local
+            .iter()
+            .filter(|(key, entry)| {
+                self.synced
+                    .get(*key)
+                    .map(|synced| !synced.same_content(entry))
+                    .unwrap_or(true)
+            })
+            .map(|(_, entry)| entry.clone())
+            .collect::<Vec<_>>();
+
+        changes.extend(
+            self.synced
+                .iter()
+                .filter(|(key, synced)| !synced.deleted && !local.contains_key(*key))
+                .map(|(_, synced)| CollabEntry {
+                    deleted: true,
+                    ..synced.clone()
+                }),
+        );
+
+        changes
+    }
+
+    /// merges remote entries into the synced state and the worksheets
+    ///
+    /// local edits which are not synced yet are kept
+    pub fn apply(&mut self, wk: &WorkSheetsFormState, remote: Vec<CollabEntry>) {
+        let pending = if self.joining {
+            HashMap::new()
+        } else {
+            self.changes(wk)
+                .into_iter()
+                .map(|entry| (entry.key(), entry))
+                .collect::<HashMap<_, _>>()
+        };
+
+        for entry in remote {
+            self.revision = self.revision.max(entry.revision);
+            self.synced.insert(entry.key(), entry);
+        }
+
+        let value = |field: &str| {
+            let key = (field.to_string(), Uuid::nil());
+            self.synced
+                .get(&key)
+                .filter(|_| !pending.contains_key(&key))
+                .filter(|e| !e.deleted)
+                .map(|e| e.value.clone())
+        };
+        let list = |field: &str, current: &[FormState<String>], entry: EntryFn<String>| {
+            merge_list(field, current, entry, &self.synced, &pending)
+        };
+
+        let set_string = |state: FormState<String>, field: &str| {
+            if let Some(v) = value(field) {
+                if state.get_untracked() != v {
+                    state.set(v);
+                }
+            }
+        };
+
+        wk.problem.update(|p| {
+            p.problems = list(
+                "problem.problems",
+                &p.problems,
+                ProblemWKFormState::problems_entry,
+            );
+            p.stakeholders = merge_list(
+                "problem.stakeholders",
+                &p.stakeholders,
+                ProblemWKFormState::stakeholders_entry,
+                &self.synced,
+                &pending,
+            );
+        });
+        let problem = wk.problem.get_untracked();
+        set_string(problem.problem_statement, "problem.problem_statement");
+        set_json(problem.clusters, value("problem.clusters"));
+
+        wk.solutions.update(|s| {
+            s.solutions = list(
+                "solutions.solutions",
+                &s.solutions,
+                SolutionsWKFormState::solutions_entry,
+            );
+        });
+        set_json(
+            wk.solutions.get_untracked().ratings,
+            value("solutions.ratings"),
+        );
+
+        let compromise = wk.compromise.get_untracked();
+        set_json(
+            compromise.solution_choices,
+            value("compromise.solution_choices"),
+        );
+        set_json(
+            compromise.stakeholder_choices,
+            value("compromise.stakeholder_choices"),
+        );
+        set_string(compromise.question, "compromise.question");
+
+        wk.implement.update(|i| {
+            i.now = list("implement.now", &i.now, |id, v| {
+                with_id(id, ImplementWKFormState::now_entry(v))
+            });
+            i.best = list("implement.best", &i.best, |id, v| {
+                with_id(id, ImplementWKFormState::best_entry(v))
+            });
+        });
+
+        wk.iterate.update(|i| {
+            i.resources = list("iterate.resources", &i.resources, |id, v| {
+                with_id(id, IterateWKFormState::resources_entry(v))
+            });
+            i.external_resources = list(
+                "iterate.external_resources",
+                &i.external_resources,
+                |id, v| with_id(id, IterateWKFormState::external_resources_entry(v)),
+            );
+        });
+        let iterate = wk.iterate.get_untracked();
+        set_string(iterate.title, "iterate.title");
+        set_json(iterate.start_date, value("iterate.start_date"));
+        set_json(iterate.end_date, value("iterate.end_date"));
+        set_json(iterate.milestones, value("iterate.milestones"));
+
+        wk.reflect.update(|r| {
+            r.outcomes = list("reflect.outcomes", &r.outcomes, |id, v| {
+                with_id(id, ReflectWKFormState::outcomes_entry(v))
+            });
+            r.learnings = list("reflect.learnings", &r.learnings, |id, v| {
+                with_id(id, ReflectWKFormState::learnings_entry(v))
+            });
+        });
+        let reflect = wk.reflect.get_untracked();
+        set_string(reflect.statement_review, "reflect.statement_review");
+        set_string(reflect.revised_statement, "reflect.revised_statement");
+        set_json(wk.iterations.clone(), value("iterations"));
+        set_string(wk.template.clone(), "template");
+        set_json(wk.custom.clone(), value("custom"));
+
+        self.joining = false;
+    }
+}
+
+/// flattens the shared parts of the worksheets into entries
+///
+/// personal data of the inquire step is never synced
+pub fn collab_entries(wk: &WorkSheetsFormState) -> HashMap<CollabKey, CollabEntry> {
+    let mut entries = HashMap::new();
+
+    let mut single = |field: &str, value: String| {
+        let entry = CollabEntry {
+            field: field.to_string(),
+            id: Uuid::nil(),
+            position: 0,
+            value,
+            deleted: false,
+            revision: 0,
+        };
+        entries.insert(entry.key(), entry);
+    };
+
+    let problem = wk.problem.get_untracked();
+    let compromise = wk.compromise.get_untracked();
+    let iterate = wk.iterate.get_untracked();
+    let reflect = wk.reflect.get_untracked();
+
+    single(
+        "problem.problem_statement",
+        problem.problem_statement.get_untracked(),
+    );
+    single(
+        "problem.clusters",
+        serde_json::to_string(&problem.clusters.get_untracked()).unwrap_or_default(),
+    );
+    single(
+        "compromise.solution_choices",
+        serde_json::to_string(&compromise.solution_choices.get_untracked()).unwrap_or_default(),
+    );
+    single(
+        "compromise.stakeholder_choices",
+        serde_json::to_string(&compromise.stakeholder_choices.get_untracked()).unwrap_or_default(),
+    );
+    single("compromise.question", compromise.question.get_untracked());
+    single("iterate.title", iterate.title.get_untracked());
+    single(
+        "iterate.start_date",
+        serde_json::to_string(&iterate.start_date.get_untracked()).unwrap_or_default(),
+    );
+    single(
+        "iterate.end_date",
+        serde_json::to_string(&iterate.end_date.get_untracked()).unwrap_or_default(),
+    );
+    single(
+        "iterate.milestones",
+        serde_json::to_string(&iterate.milestones.get_untracked()).unwrap_or_default(),
+    );
+    single(
+        "reflect.statement_review",
+        reflect.statement_review.get_untracked(),
+    );
+    single(
+        "reflect.revised_statement",
+        reflect.revised_statement.get_untracked(),
+    );
+    single(
+        "iterations",
+        serde_json::to_string(&wk.iterations.get_untracked()).unwrap_or_default(),
+    );
+    single("template", wk.template.get_untracked());
+    single(
+        "custom",
+        serde_json::to_string(&wk.custom.get_untracked()).unwrap_or_default(),
+    );
+
+    let solutions = wk.solutions.get_untracked();
+    let implement = wk.implement.get_untracked();
+
+    single(
+        "solutions.ratings",
+        serde_json::to_string(&solutions.ratings.get_untracked()).unwrap_or_default(),
+    );
+
+    list_entries(&mut entries, "problem.stakeholders", &problem.stakeholders);
+    for (field, list) in [
+        ("problem.problems", &problem.problems),
+        ("solutions.solutions", &solutions.solutions),
+        ("implement.now", &implement.now),
+        ("implement.best", &implement.best),
+        ("iterate.resources", &iterate.resources),
+        ("iterate.external_resources", &iterate.external_resources),
+        ("reflect.outcomes", &reflect.outcomes),
+        ("reflect.learnings", &reflect.learnings),
+    ] {
+        list_entries(&mut entries, field, list);
+    }
+
+    entries
+}
+
+fn list_entries<T: CollabValue>(
+    entries: &mut HashMap<CollabKey, CollabEntry>,
+    field: &str,
+    list: &[FormState<T>],
+) {
+    for (position, item) in list.iter().enumerate() {
+        let entry = CollabEntry {
+            field: field.to_string(),
+            id: item.id,
+            position: position as u32,
+            value: item.get_untracked().to_synced(),
+            deleted: false,
+            revision: 0,
+        };
+        entries.insert(entry.key(), entry);
+    }
+}
+
+/// sets a value synced as json
+fn set_json<T>(state: FormState<T>, value: Option<String>)
+where
+    T: DeserializeOwned + std::fmt::Debug + Default + PartialEq + Clone + 'static,
+{
+    if let Some(v) = value.and_then(|v| serde_json::from_str::<T>(v.as_str()).ok()) {
+        if state.get_untracked() != v {
+            state.set(v);
+        }
+    }
+}
+
+/// value of a list entry as synced, text is kept as is and structured entries as json
+trait CollabValue: std::fmt::Debug + Default + PartialEq + Clone + 'static {
+    fn to_synced(&self) -> String;
+    fn from_synced(value: &str) -> Self;
+}
+
+impl CollabValue for String {
+    fn to_synced(&self) -> String {
+        self.clone()
+    }
+
+    fn from_synced(value: &str) -> Self {
+        value.to_string()
+    }
+}
+
+impl CollabValue for Stakeholder {
+    fn to_synced(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+
+    fn from_synced(value: &str) -> Self {
+        serde_json::from_str(value).unwrap_or_else(|_| Stakeholder::from(value.to_string()))
+    }
+}
+
+/// constructor of list entries which carry the validators of the list
+type EntryFn<T> = fn(Uuid, T) -> FormState<T>;
+
+/// keeps the synced id of an entry of an iterable list
+fn with_id(id: Uuid, mut state: FormState<String>) -> FormState<String> {
+    state.id = id;
+    state
+}
+
+/// rebuilds a list from synced entries reusing existing `FormState`s by id
+fn merge_list<T: CollabValue>(
+    field: &str,
+    current: &[FormState<T>],
+    entry_fn: EntryFn<T>,
+    synced: &HashMap<CollabKey, CollabEntry>,
+    pending: &HashMap<CollabKey, CollabEntry>,
+) -> Vec<FormState<T>> {
+    // items removed locally stay removed until the removal is synced
+    let mut merged = synced
+        .values()
+        .filter(|e| e.field == field && !e.deleted)
+        .filter(|e| !pending.get(&e.key()).map(|p| p.deleted).unwrap_or(false))
+        .collect::<Vec<_>>();
+    // entries inserted at the same time share a position, all participants break the tie alike
+    merged.sort_by_key(|e| (e.position, e.revision, e.id));
+
+    let mut list = merged
+        .into_iter()
+        .map(|entry| {
+            let key = entry.key();
+            let value = T::from_synced(entry.value.as_str());
+            match current.iter().find(|s| s.id == entry.id) {
+                Some(state) => {
+                    if !pending.contains_key(&key) && state.get_untracked() != value {
+                        state.set(value);
+                    }
+                    state.clone()
+                }
+                None => entry_fn(entry.id, value),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // local items which are not synced yet stay in place
+    for (i, state) in current.iter().enumerate() {
+        let key = (field.to_string(), state.id);
+        if pending.contains_key(&key) && !list.iter().any(|s| s.id == state.id) {
+            let deleted = synced.get(&key).map(|e| e.deleted).unwrap_or(false);
+            if !deleted {
+                list.insert(i.min(list.len()), state.clone());
+            }
+        }
+    }
+
+    list
+}
@@ -0,0 +1,20 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer};
+
+/// format of dates stored in the workbook and used by date inputs
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// reads an optional date, empty or invalid dates stored as text are dropped
+pub fn deserialize_date<'de, D>(deserializer: D) -> Result<Option<NaiveDate>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Option::<String>::deserialize(deserializer)?
+        .and_then(|d| NaiveDate::parse_from_str(d.trim(), DATE_FORMAT).ok()))
+}
+
+/// date as shown in exports, empty when not set
+pub fn format_date(date: Option<NaiveDate>) -> String {
+    date.map(|d| d.format(DATE_FORMAT).to_string())
+        .unwrap_or_default()
+}
@@ -0,0 +1,155 @@
+use std::{collections::HashSet, fmt::Display};
+
+use serde::{Deserialize, Deserializer};
+use uuid::Uuid;
+
+use super::Stakeholder;
+
+/// list entry along with the id of its `FormState`
+pub type KeyedEntry = (Uuid, String);
+
+/// id of an entry stored before ids were kept
+///
+/// derived from the list and the text, so that choices stored as text resolve to the same entry
+pub fn legacy_id(list: &str, text: &str) -> Uuid {
+    Uuid::new_v5(
+        &Uuid::new_v5(&Uuid::NAMESPACE_OID, list.as_bytes()),
+        text.as_bytes(),
+    )
+}
+
+pub fn deserialize_problems<'de, D>(deserializer: D) -> Result<Vec<KeyedEntry>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_keyed("problem.problems", deserializer)
+}
+
+pub fn deserialize_stakeholders<'de, D>(
+    deserializer: D,
+) -> Result<Vec<(Uuid, Stakeholder)>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_keyed("problem.stakeholders", deserializer)
+}
+
+pub fn deserialize_solutions<'de, D>(deserializer: D) -> Result<Vec<KeyedEntry>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_keyed("solutions.solutions", deserializer)
+}
+
+pub fn deserialize_solution_choices<'de, D>(deserializer: D) -> Result<Vec<Uuid>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_choices("solutions.solutions", deserializer)
+}
+
+pub fn deserialize_stakeholder_choices<'de, D>(deserializer: D) -> Result<Vec<Uuid>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserialize_choices("problem.stakeholders", deserializer)
+}
+
+/// reads keyed entries of a list, entries stored as plain text are migrated
+fn deserialize_keyed<'de, D, T>(list: &str, deserializer: D) -> Result<Vec<(Uuid, T)>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + From<String>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Stored<T> {
+        Keyed(Uuid, T),
+        Text(Uuid, String),
+        Legacy(String),
+    }
+
+    let mut seen = HashSet::new();
+
+    Ok(Vec::<Stored<T>>::deserialize(deserializer)?
+        .into_iter()
+        .map(|entry| {
+            let (id, value) = match entry {
+                Stored::Keyed(id, value) => (id, value),
+                Stored::Text(id, value) => (id, value.into()),
+                Stored::Legacy(value) => {
+                    // repeated entries only keep the derived id once
+                    let id = Some(legacy_id(list, value.as_str()))
+                        .filter(|id| !seen.contains(id))
+                        .unwrap_or_else(Uuid::new_v4);
+                    (id, value.into())
+                }
+            };
+            seen.insert(id);
+            (id, value)
+        })
+        .collect())
+}
+
+/// reads references to keyed entries of a list, references stored as text are migrated
+fn deserialize_choices<'de, D>(list: &str, deserializer: D) -> Result<Vec<Uuid>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Stored {
+        Id(Uuid),
+        Legacy(String),
+    }
+
+    Ok(Vec::<Stored>::deserialize(deserializer)?
+        .into_iter()
+        .map(|choice| match choice {
+            Stored::Id(id) => id,
+            Stored::Legacy(value) => legacy_id(list, value.as_str()),
+        })
+        .collect())
+}
+
+/// values of keyed entries
+pub fn keyed_values<V: AsRef<str>>(entries: &[(Uuid, V)]) -> Vec<String> {
+    entries
+        .iter()
+        .map(|(_, v)| v.as_ref().to_string())
+        .collect()
+}
+
+/// resolves references to keyed entries, unknown references are skipped
+pub fn resolve_choices<V: AsRef<str>>(choices: &[Uuid], entries: &[(Uuid, V)]) -> Vec<String> {
+    choices
+        .iter()
+        .filter_map(|id| entries.iter().find(|(e_id, _)| e_id == id))
+        .map(|(_, v)| v.as_ref().to_string())
+        .collect()
+}
+
+/// references to entries which exist
+pub fn known_choices<V>(choices: &[Uuid], entries: &[(Uuid, V)]) -> Vec<Uuid> {
+    choices
+        .iter()
+        .filter(|id| entries.iter().any(|(e_id, _)| e_id == *id))
+        .copied()
+        .collect()
+}
+
+/// entry removed from a keyed list, restored with its id, the choice referring to it
+/// and the data `attached` to it in other lists
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RemovedEntry<V = String, T = ()> {
+    pub id: Uuid,
+    pub value: V,
+    pub chosen: bool,
+    pub attached: T,
+}
+
+impl<V: Display, T> Display for RemovedEntry<V, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
@@ -1,11 +1,21 @@
+mod collab;
+mod dates;
 mod examples;
+mod keyed;
+mod templates;
 mod types;
+mod validation;
 mod worksheets;
 
 use form_signal::FormState;
 
+pub use collab::*;
+pub use dates::*;
 pub use examples::*;
+pub use keyed::*;
+pub use templates::*;
 pub use types::*;
+pub use validation::*;
 pub use worksheets::*;
 
 use leptos::*;
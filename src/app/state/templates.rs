@@ -0,0 +1,174 @@
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+
+use super::{CustomAnswer, ProcessStep, ValidationReport, WorkSheets};
+
+pub const DEFAULT_TEMPLATE: &str = "standard";
+
+// the json files of the templates directory are compiled in by build.rs, the client renders the
+// steps without a server round trip, so adding a template needs a rebuild
+include!(concat!(env!("OUT_DIR"), "/templates.rs"));
+
+static PARSED_TEMPLATES: OnceLock<Vec<ProcessTemplate>> = OnceLock::new();
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessTemplate {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub steps: Vec<TemplateStep>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateStep {
+    pub id: String,
+    pub kind: ProcessStep,
+    /// localization key of the title, the stepper label of the kind by default
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// animation artboard, the one of the kind by default
+    #[serde(default)]
+    pub artboard: Option<String>,
+    #[serde(default)]
+    pub examples: bool,
+    /// whether the step counts towards completeness of the workbook
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub fields: Vec<TemplateField>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateField {
+    pub id: String,
+    pub kind: FieldKind,
+    pub label: String,
+    #[serde(default)]
+    pub placeholder: Option<String>,
+    /// number of non empty entries a complete field holds
+    #[serde(default)]
+    pub min_entries: usize,
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FieldKind {
+    #[default]
+    Text,
+    Textarea,
+    List,
+}
+
+impl ProcessTemplate {
+    /// parsed once, the default template comes first, invalid templates are left out
+    pub fn all() -> &'static [ProcessTemplate] {
+        PARSED_TEMPLATES.get_or_init(|| {
+            let mut all = TEMPLATES
+                .iter()
+                .filter_map(|t| {
+                    serde_json::from_str::<ProcessTemplate>(t)
+                        .map_err(|e| log::error!("invalid process template: {e}"))
+                        .ok()
+                })
+                .collect::<Vec<_>>();
+            all.sort_by_key(|t| t.id != DEFAULT_TEMPLATE);
+            all
+        })
+    }
+
+    pub fn by_id(id: &str) -> &'static ProcessTemplate {
+        static EMPTY: ProcessTemplate = ProcessTemplate {
+            id: String::new(),
+            title: String::new(),
+            description: String::new(),
+            steps: Vec::new(),
+        };
+        let all = Self::all();
+        all.iter()
+            .find(|t| t.id == id)
+            .or_else(|| all.first())
+            .unwrap_or(&EMPTY)
+    }
+
+    pub fn step(&self, path: &str) -> Option<&TemplateStep> {
+        self.steps.iter().find(|s| s.path() == path)
+    }
+
+    pub fn next_path(&self, path: &str) -> Option<String> {
+        let pos = self.steps.iter().position(|s| s.path() == path)?;
+        self.steps.get(pos + 1).map(|s| s.path())
+    }
+
+    pub fn validate(&self, wk: &WorkSheets) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        self.steps
+            .iter()
+            .filter(|s| s.required)
+            .filter_map(|s| s.report(wk))
+            .for_each(|r| report.extend(r));
+        report
+    }
+}
+
+impl TemplateStep {
+    /// route segment, the number of a worksheet or the id of a custom step
+    pub fn path(&self) -> String {
+        match self.kind {
+            ProcessStep::Custom => self.id.clone(),
+            kind => (kind as usize).to_string(),
+        }
+    }
+
+    pub fn title(&self) -> String {
+        self.title
+            .clone()
+            .unwrap_or_else(|| format!("stepper.{}", self.kind.to_string().to_lowercase()))
+    }
+
+    pub fn artboard(&self) -> String {
+        self.artboard
+            .clone()
+            .unwrap_or_else(|| self.kind.artboard())
+    }
+
+    pub fn report(&self, wk: &WorkSheets) -> Option<ValidationReport> {
+        if self.kind != ProcessStep::Custom {
+            return wk.step_report(self.kind);
+        }
+
+        let mut report = ValidationReport::default();
+        for field in self.fields.iter().filter(|f| f.min_entries > 0) {
+            let entries = wk
+                .custom_answer(self.id.as_str(), field.id.as_str())
+                .map(|a| a.values.iter().filter(|v| !v.is_empty()).count())
+                .unwrap_or_default();
+            report.check_custom(
+                self.path(),
+                format!("{}.{}", self.id, field.id),
+                entries >= field.min_entries,
+                field
+                    .message
+                    .clone()
+                    .unwrap_or_else(|| "worksheets.validation.custom".to_string()),
+            );
+        }
+        Some(report)
+    }
+
+    pub fn answers(&self, wk: &WorkSheets) -> Vec<(TemplateField, CustomAnswer)> {
+        self.fields
+            .iter()
+            .map(|f| {
+                let answer = wk
+                    .custom_answer(self.id.as_str(), f.id.as_str())
+                    .cloned()
+                    .unwrap_or_else(|| CustomAnswer::new(self.id.as_str(), f.id.as_str()));
+                (f.clone(), answer)
+            })
+            .collect()
+    }
+}
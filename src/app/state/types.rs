@@ -6,7 +6,9 @@ use strum::{Display, EnumString, VariantArray};
 
 use crate::app::Language;
 
-use super::{worksheets::*, ProjectData};
+use super::{
+    worksheets::*, CollabSession, Completenes, ProcessTemplate, ProjectData, ValidationReport,
+};
 
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct AppState {
@@ -16,6 +18,14 @@ pub struct AppState {
     pub storage_preference: FormState<Option<StorageMode>>,
     pub show_privacy_prompt: RwSignal<bool>,
     pub lang: Language,
+    pub collab: RwSignal<Option<CollabSession>>,
+}
+
+impl AppState {
+    /// process template of the worksheets
+    pub fn template(&self) -> &'static ProcessTemplate {
+        ProcessTemplate::by_id(self.wk.template.get().as_str())
+    }
 }
 
 #[derive(
@@ -41,11 +51,15 @@ impl Into<StorageType> for &StorageMode {
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct SeqStep {
     pub href: String,
+    /// route segment of the template step
+    pub path: String,
     pub process_step: ProcessStep,
     pub example: Option<String>,
 }
 
-#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Display, VariantArray, Hash)]
+#[derive(
+    Default, Debug, Clone, Copy, PartialEq, Eq, Display, VariantArray, Hash, Serialize, Deserialize,
+)]
 pub enum ProcessStep {
     #[default]
     About = 0,
@@ -55,18 +69,55 @@ pub enum ProcessStep {
     Implement = 4,
     Iterate = 5,
     Inquire = 6,
+    // numbered after inquire to keep the existing step links
+    Reflect = 7,
+    /// step with fields defined by a process template
+    Custom = 8,
+}
+
+impl ProcessStep {
+    /// name of the animation artboard of the step
+    pub fn artboard(&self) -> String {
+        match self {
+            ProcessStep::Reflect => ProcessStep::Iterate.to_string(),
+            ProcessStep::Custom => ProcessStep::About.to_string(),
+            step => step.to_string(),
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone, PartialEq, FormState, Eq, Serialize, Deserialize, Hash)]
 pub struct Contact {
+    #[validate(required, length(max = 200))]
     pub name: String,
+    #[validate(required, email)]
     pub email: String,
+    #[validate(required, length(max = 5000))]
     pub message: String,
 }
 
 impl Completenes for Contact {
-    fn is_complete(&self) -> bool {
-        !self.name.is_empty() && !self.email.is_empty() && !self.message.is_empty()
+    fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        report.check(
+            ProcessStep::Inquire,
+            "inquire.contact.name",
+            !self.name.is_empty(),
+            "worksheets.validation.name",
+        );
+        report.check(
+            ProcessStep::Inquire,
+            "inquire.contact.email",
+            !self.email.is_empty(),
+            "worksheets.validation.email",
+        );
+        report.check(
+            ProcessStep::Inquire,
+            "inquire.contact.message",
+            !self.message.is_empty(),
+            "worksheets.validation.message",
+        );
+        report
     }
 
     fn is_empty(&self) -> bool {
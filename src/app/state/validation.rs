@@ -0,0 +1,97 @@
+use super::ProcessStep;
+
+/// a missing or invalid field of the worksheets
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    pub step: ProcessStep,
+    /// route segment of the step
+    pub path: String,
+    /// path of the field, e.g. `problem.problem_statement`
+    pub field: String,
+    /// localization key of the message
+    pub message: String,
+}
+
+impl ValidationIssue {
+    /// id of the element holding the field
+    pub fn anchor(&self) -> String {
+        self.field.replace('.', "-")
+    }
+
+    pub fn message(&self) -> String {
+        t!(self.message.as_str()).to_string()
+    }
+}
+
+/// outcome of validating the worksheets or one of their steps
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// number of checked fields
+    pub checked: usize,
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// records the outcome of a single field check
+    pub fn check(
+        &mut self,
+        step: ProcessStep,
+        field: &'static str,
+        valid: bool,
+        message: &'static str,
+    ) {
+        self.checked += 1;
+        if !valid {
+            self.issues.push(ValidationIssue {
+                step,
+                path: (step as usize).to_string(),
+                field: field.to_string(),
+                message: message.to_string(),
+            });
+        }
+    }
+
+    /// records the outcome of a field check of a custom step
+    pub fn check_custom(&mut self, path: String, field: String, valid: bool, message: String) {
+        self.checked += 1;
+        if !valid {
+            self.issues.push(ValidationIssue {
+                step: ProcessStep::Custom,
+                path,
+                field: format!("custom.{field}"),
+                message,
+            });
+        }
+    }
+
+    pub fn extend(&mut self, other: ValidationReport) {
+        self.checked += other.checked;
+        self.issues.extend(other.issues);
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// number of checked fields without issues
+    pub fn passed(&self) -> usize {
+        self.checked.saturating_sub(self.issues.len())
+    }
+}
+
+pub trait Completenes {
+    fn validate(&self) -> ValidationReport;
+
+    fn is_complete(&self) -> bool {
+        self.validate().is_valid()
+    }
+
+    fn is_empty(&self) -> bool {
+        !self.is_complete()
+    }
+}
+
+/// whether a list holds at least one non empty entry
+pub fn has_entries(list: &[String]) -> bool {
+    list.iter().any(|e| !e.is_empty())
+}
@@ -1,10 +1,20 @@
 use std::{collections::HashSet, str::FromStr};
 
-use leptos::SignalSet;
+use chrono::NaiveDate;
+use form_signal::FormState;
+use leptos::{SignalGetUntracked, SignalSet, SignalUpdate};
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumString, VariantArray};
+use uuid::Uuid;
 
-use super::{Contact, ContactFormState};
+use crate::app::components::TextValue;
+
+use super::{
+    deserialize_date, deserialize_problems, deserialize_solution_choices, deserialize_solutions,
+    deserialize_stakeholder_choices, deserialize_stakeholders, has_entries, keyed_values,
+    known_choices, legacy_id, resolve_choices, Completenes, Contact, ContactFormState, KeyedEntry,
+    ProcessStep, ValidationReport, DEFAULT_TEMPLATE,
+};
 
 #[derive(FormState, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
 pub struct WorkSheets {
@@ -20,6 +30,19 @@ pub struct WorkSheets {
     pub iterate: IterateWK,
     #[nested]
     pub inquire: InquireWK,
+    #[nested]
+    #[serde(default)]
+    pub reflect: ReflectWK,
+    #[serde(default)]
+    pub iterations: Vec<IterationRecord>,
+    #[serde(default = "default_template")]
+    pub template: String,
+    #[serde(default)]
+    pub custom: Vec<CustomAnswer>,
+}
+
+fn default_template() -> String {
+    DEFAULT_TEMPLATE.to_string()
 }
 
 impl WorkSheetsFormState {
@@ -31,6 +54,185 @@ impl WorkSheetsFormState {
         self.implement.set(d.implement.into());
         self.iterate.set(d.iterate.into());
         self.inquire.set(d.inquire.into());
+        self.reflect.set(d.reflect.into());
+        self.iterations.set(d.iterations);
+        self.custom.set(d.custom);
+    }
+
+    pub fn set_custom(&self, step: &str, field: &str, f: impl FnOnce(&mut Vec<String>)) {
+        self.custom.update(|answers| {
+            let pos = answers
+                .iter()
+                .position(|a| a.step == step && a.field == field)
+                .unwrap_or_else(|| {
+                    answers.push(CustomAnswer::new(step, field));
+                    answers.len() - 1
+                });
+            f(&mut answers[pos].values);
+        });
+    }
+}
+
+impl WorkSheets {
+    pub fn step_report(&self, step: ProcessStep) -> Option<ValidationReport> {
+        match step {
+            ProcessStep::About => None,
+            ProcessStep::Problem => Some(self.problem.validate()),
+            ProcessStep::Solution => Some(self.solutions.validate()),
+            ProcessStep::Compromise => Some(self.compromise_report()),
+            ProcessStep::Implement => Some(self.implement.validate()),
+            ProcessStep::Iterate => Some(self.iterate.validate()),
+            ProcessStep::Inquire => Some(self.inquire.validate()),
+            ProcessStep::Reflect => Some(self.reflect.validate()),
+            ProcessStep::Custom => None,
+        }
+    }
+
+    /// choices of entries which were removed since don't count
+    fn compromise_report(&self) -> ValidationReport {
+        CompromiseWK {
+            solution_choices: known_choices(
+                &self.compromise.solution_choices,
+                &self.solutions.solutions,
+            ),
+            stakeholder_choices: known_choices(
+                &self.compromise.stakeholder_choices,
+                &self.problem.stakeholders,
+            ),
+            ..self.compromise.clone()
+        }
+        .validate()
+    }
+
+    pub fn custom_answer(&self, step: &str, field: &str) -> Option<&CustomAnswer> {
+        self.custom
+            .iter()
+            .find(|a| a.step == step && a.field == field)
+    }
+
+    /// keeps the current iteration in the series and prepares the next one from it
+    ///
+    /// the next iteration starts the day after the deadline of the current one
+    pub fn next_iteration(&self) -> WorkSheets {
+        let d = WorkSheets::default();
+        let mut next = self.clone();
+
+        next.iterations.push(IterationRecord {
+            problem: self.problem.clone(),
+            solutions: self.solutions.clone(),
+            compromise: self.compromise.clone(),
+            implement: self.implement.clone(),
+            iterate: self.iterate.clone(),
+            reflect: self.reflect.clone(),
+            custom: self.custom.clone(),
+        });
+
+        if let Ok(StatementReview::Revise) =
+            StatementReview::from_str(self.reflect.statement_review.as_str())
+        {
+            if !self.reflect.revised_statement.is_empty() {
+                next.problem.problem_statement = self.reflect.revised_statement.clone();
+            }
+        }
+
+        next.iterate = IterateWK {
+            start_date: self.iterate.end_date.and_then(|end| end.succ_opt()),
+            resources: self.iterate.resources.clone(),
+            external_resources: self.iterate.external_resources.clone(),
+            ..d.iterate
+        };
+        next.reflect = d.reflect;
+
+        next
+    }
+
+    pub fn solution_choices(&self) -> Vec<String> {
+        resolve_choices(&self.compromise.solution_choices, &self.solutions.solutions)
+    }
+
+    pub fn stakeholder_choices(&self) -> Vec<String> {
+        resolve_choices(
+            &self.compromise.stakeholder_choices,
+            &self.problem.stakeholders,
+        )
+    }
+
+    /// worksheets as json without entry ids, as read by people and language models
+    pub fn to_plain_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&self.to_plain_value()?)
+    }
+
+    fn to_plain_value(&self) -> serde_json::Result<serde_json::Value> {
+        let mut value = serde_json::to_value(self)?;
+        value["problem"]["problems"] = keyed_values(&self.problem.problems).into();
+        value["problem"]["stakeholders"] = keyed_values(&self.problem.stakeholders).into();
+        value["problem"]["clusters"] = self
+            .problem
+            .themes()
+            .into_iter()
+            .map(|(name, problems)| serde_json::json!({ "theme": name, "problems": problems }))
+            .collect::<Vec<_>>()
+            .into();
+        value["problem"]["stakeholder_map"] = self
+            .problem
+            .unique_stakeholder_entries()
+            .into_iter()
+            .filter(|(_, s)| !s.role.is_empty() || s.is_placed())
+            .map(|(_, s)| {
+                serde_json::json!({
+                    "stakeholder": s.name,
+                    "role": s.role,
+                    "influence": s.influence,
+                    "interest": s.interest,
+                })
+            })
+            .collect::<Vec<_>>()
+            .into();
+        value["solutions"]["solutions"] = keyed_values(&self.solutions.solutions).into();
+        value["solutions"]["ratings"] = self
+            .solutions
+            .ratings
+            .iter()
+            .filter_map(|r| {
+                let (_, name) = self
+                    .solutions
+                    .solutions
+                    .iter()
+                    .find(|(id, _)| id == &r.id)?;
+                let stakeholders = r
+                    .stakeholders
+                    .iter()
+                    .filter_map(|(id, rating)| {
+                        let (_, s) = self.problem.stakeholders.iter().find(|(s, _)| s == id)?;
+                        Some(serde_json::json!({ "stakeholder": s.name, "benefit": rating }))
+                    })
+                    .collect::<Vec<_>>();
+                Some(serde_json::json!({
+                    "solution": name,
+                    "impact": r.impact,
+                    "effort": r.effort,
+                    "risk": r.risk,
+                    "stakeholders": stakeholders,
+                }))
+            })
+            .collect::<Vec<_>>()
+            .into();
+        value["compromise"]["solution_choices"] = self.solution_choices().into();
+        value["compromise"]["stakeholder_choices"] = self.stakeholder_choices().into();
+        value["iterations"] = self
+            .iterations
+            .iter()
+            .map(|r| {
+                let mut iteration = WorkSheets::from(r.clone()).to_plain_value()?;
+                if let Some(iteration) = iteration.as_object_mut() {
+                    iteration.remove("inquire");
+                    iteration.remove("iterations");
+                }
+                Ok(iteration)
+            })
+            .collect::<serde_json::Result<Vec<_>>>()?
+            .into();
+        Ok(value)
     }
 }
 
@@ -42,12 +244,13 @@ impl std::fmt::Display for WorkSheets {
 }
 
 impl Completenes for WorkSheets {
-    fn is_complete(&self) -> bool {
-        self.problem.is_complete()
-            && self.solutions.is_complete()
-            && self.compromise.is_complete()
-            && self.implement.is_complete()
-            && self.iterate.is_complete()
+    fn validate(&self) -> ValidationReport {
+        let mut report = self.problem.validate();
+        report.extend(self.solutions.validate());
+        report.extend(self.compromise_report());
+        report.extend(self.implement.validate());
+        report.extend(self.iterate.validate());
+        report
     }
 
     fn is_empty(&self) -> bool {
@@ -59,16 +262,20 @@ impl Completenes for WorkSheets {
     }
 }
 
+/// blank entries have ids derived from their list, so that defaults compare equal
 impl Default for WorkSheets {
     fn default() -> Self {
         Self {
             problem: ProblemWK {
-                problems: vec![Default::default()],
-                stakeholders: vec![Default::default()],
+                problems: vec![(legacy_id("problem.problems", ""), String::default())],
+                stakeholders: vec![(
+                    legacy_id("problem.stakeholders", ""),
+                    Stakeholder::default(),
+                )],
                 ..Default::default()
             },
             solutions: SolutionsWK {
-                solutions: vec![Default::default()],
+                solutions: vec![(legacy_id("solutions.solutions", ""), String::default())],
                 ..Default::default()
             },
             compromise: CompromiseWK {
@@ -84,34 +291,138 @@ impl Default for WorkSheets {
                 ..Default::default()
             },
             inquire: Default::default(),
+            reflect: ReflectWK {
+                outcomes: vec![Default::default()],
+                learnings: vec![Default::default()],
+                ..Default::default()
+            },
+            iterations: vec![],
+            template: default_template(),
+            custom: vec![],
         }
     }
 }
 
 #[derive(FormState, Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
 pub struct ProblemWK {
-    #[iterable]
-    pub problems: Vec<String>,
-    #[iterable]
-    pub stakeholders: Vec<String>,
+    #[keyed]
+    #[serde(deserialize_with = "deserialize_problems")]
+    pub problems: Vec<(Uuid, String)>,
+    #[keyed]
+    #[serde(deserialize_with = "deserialize_stakeholders")]
+    pub stakeholders: Vec<(Uuid, Stakeholder)>,
     pub problem_statement: String,
+    #[serde(default)]
+    pub clusters: Vec<ProblemCluster>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub struct ProblemCluster {
+    pub id: Uuid,
+    pub name: String,
+    pub problems: Vec<Uuid>,
+}
+
+impl ProblemCluster {
+    pub fn new() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            ..Default::default()
+        }
+    }
+}
+
+pub const STAKEHOLDER_LEVELS: u8 = 3;
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub struct Stakeholder {
+    pub name: String,
+    #[serde(default)]
+    pub role: String,
+    /// from 1 to `STAKEHOLDER_LEVELS`, none until placed on the grid
+    #[serde(default)]
+    pub influence: Option<u8>,
+    #[serde(default)]
+    pub interest: Option<u8>,
+}
+
+impl From<String> for Stakeholder {
+    fn from(name: String) -> Self {
+        Self {
+            name,
+            ..Default::default()
+        }
+    }
+}
+
+impl AsRef<str> for Stakeholder {
+    fn as_ref(&self) -> &str {
+        self.name.as_str()
+    }
+}
+
+impl std::fmt::Display for Stakeholder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl TextValue for Stakeholder {
+    fn text(&self) -> String {
+        self.name.clone()
+    }
+
+    fn set_text(&mut self, text: String) {
+        self.name = text;
+    }
+}
+
+impl Stakeholder {
+    pub fn is_placed(&self) -> bool {
+        self.influence.is_some() && self.interest.is_some()
+    }
+
+    /// stakeholders with both more influence and interest come first
+    pub fn priority(&self) -> u8 {
+        self.influence.unwrap_or_default() * self.interest.unwrap_or_default()
+    }
+
+    /// localization key of the engagement strategy of a grid cell
+    pub fn strategy(influence: u8, interest: u8) -> Option<&'static str> {
+        let high = |level: u8| level == STAKEHOLDER_LEVELS;
+        let low = |level: u8| level == 1;
+        match (influence, interest) {
+            (i, j) if high(i) && high(j) => Some("worksheets.problem.map.manage"),
+            (i, j) if high(i) && low(j) => Some("worksheets.problem.map.satisfy"),
+            (i, j) if low(i) && high(j) => Some("worksheets.problem.map.inform"),
+            (i, j) if low(i) && low(j) => Some("worksheets.problem.map.monitor"),
+            _ => None,
+        }
+    }
 }
 
 impl Completenes for ProblemWK {
-    fn is_complete(&self) -> bool {
-        !self.problem_statement.is_empty()
-            && self
-                .problems
-                .iter()
-                .filter(|r| !r.is_empty())
-                .next()
-                .is_some()
-            && self
-                .stakeholders
-                .iter()
-                .filter(|r| !r.is_empty())
-                .next()
-                .is_some()
+    fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        report.check(
+            ProcessStep::Problem,
+            "problem.problems",
+            has_entries(&keyed_values(&self.problems)),
+            "worksheets.validation.problems",
+        );
+        report.check(
+            ProcessStep::Problem,
+            "problem.stakeholders",
+            has_entries(&keyed_values(&self.stakeholders)),
+            "worksheets.validation.stakeholders",
+        );
+        report.check(
+            ProcessStep::Problem,
+            "problem.problem_statement",
+            !self.problem_statement.is_empty(),
+            "worksheets.validation.problem_statement",
+        );
+        report
     }
 
     fn is_empty(&self) -> bool {
@@ -119,13 +430,13 @@ impl Completenes for ProblemWK {
             && self
                 .problems
                 .iter()
-                .filter(|r| !r.is_empty())
+                .filter(|(_, r)| !r.is_empty())
                 .next()
                 .is_none()
             && self
                 .stakeholders
                 .iter()
-                .filter(|r| !r.is_empty())
+                .filter(|(_, r)| !r.name.is_empty())
                 .next()
                 .is_none()
     }
@@ -133,78 +444,354 @@ impl Completenes for ProblemWK {
 
 impl ProblemWK {
     pub fn unique_stakeholders(&self) -> Vec<String> {
-        let all_unique = HashSet::<String>::from_iter(self.stakeholders.iter().filter_map(|s| {
-            if !s.is_empty() {
-                Some(s.clone())
-            } else {
-                None
-            }
-        }));
-        let mut entries = Vec::from_iter(all_unique);
-        entries.sort_by(|a, b| {
-            let a_w = self.stakeholders.iter().filter(|s| s == &a).count();
-            let b_w = self.stakeholders.iter().filter(|s| s == &b).count();
-            b_w.cmp(&a_w)
+        keyed_values(&self.unique_stakeholder_entries())
+    }
+
+    /// stakeholders named more than once are represented by their first entry
+    ///
+    /// ordered by their place on the power/interest grid, then by how often they are named
+    pub fn unique_stakeholder_entries(&self) -> Vec<(Uuid, Stakeholder)> {
+        let mut seen = HashSet::new();
+        let mut entries = self
+            .stakeholders
+            .iter()
+            .filter(|(_, s)| !s.name.is_empty() && seen.insert(s.name.clone()))
+            .cloned()
+            .collect::<Vec<_>>();
+        let weight = |name: &str| {
+            self.stakeholders
+                .iter()
+                .filter(|(_, s)| s.name == name)
+                .count()
+        };
+        entries.sort_by(|(_, a), (_, b)| {
+            b.priority()
+                .cmp(&a.priority())
+                .then(weight(&b.name).cmp(&weight(&a.name)))
         });
 
         entries
     }
+
+    pub fn themes(&self) -> Vec<(String, Vec<String>)> {
+        self.clusters
+            .iter()
+            .filter(|c| !c.name.is_empty())
+            .map(|c| {
+                let problems = resolve_choices(&c.problems, &self.problems)
+                    .into_iter()
+                    .filter(|p| !p.is_empty())
+                    .collect();
+                (c.name.clone(), problems)
+            })
+            .collect()
+    }
+
+    pub fn stakeholder(&self, id: Uuid) -> Stakeholder {
+        self.stakeholders
+            .iter()
+            .find(|(s_id, _)| *s_id == id)
+            .map(|(_, s)| s.clone())
+            .unwrap_or_default()
+    }
+
+    pub fn unclustered(&self) -> Vec<KeyedEntry> {
+        self.problems
+            .iter()
+            .filter(|(id, p)| {
+                !p.is_empty() && !self.clusters.iter().any(|c| c.problems.contains(id))
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+impl ProblemWKFormState {
+    pub fn update_stakeholder(&self, id: Uuid, f: impl FnOnce(&mut Stakeholder)) {
+        if let Some(entry) = self.stakeholders.iter().find(|s| s.id == id) {
+            entry.update(f);
+        }
+    }
+
+    /// moves a problem behind `after` in a cluster, a nil `after` puts it first
+    pub fn move_to_cluster(&self, id: Uuid, cluster: Option<Uuid>, after: Uuid) {
+        self.clusters.update(|clusters| {
+            clusters
+                .iter_mut()
+                .for_each(|c| c.problems.retain(|p| *p != id));
+            if let Some(c) = clusters.iter_mut().find(|c| Some(c.id) == cluster) {
+                match c.problems.iter().position(|p| *p == after) {
+                    _ if after.is_nil() => c.problems.insert(0, id),
+                    Some(pos) => c.problems.insert(pos + 1, id),
+                    None => c.problems.push(id),
+                }
+            }
+        });
+    }
+
+    /// cluster of a problem and the problem before it, nil when it is first
+    pub fn cluster_of(&self, id: Uuid) -> Option<(Uuid, Uuid)> {
+        self.clusters.get_untracked().iter().find_map(|c| {
+            let pos = c.problems.iter().position(|p| *p == id)?;
+            let after = pos.checked_sub(1).map(|i| c.problems[i]);
+            Some((c.id, after.unwrap_or_default()))
+        })
+    }
+
+    /// hands the role and place of a removed entry to another entry of the same stakeholder
+    pub fn move_profile(&self, id: Uuid, to: Uuid) {
+        let Some(from) = self.stakeholders.iter().find(|s| s.id == id) else {
+            return;
+        };
+        let from = from.get_untracked();
+        self.update_stakeholder(to, |s| {
+            if s.role.is_empty() {
+                s.role = from.role;
+            }
+            if !s.is_placed() {
+                s.influence = from.influence;
+                s.interest = from.interest;
+            }
+        });
+    }
 }
 
 #[derive(FormState, Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
 pub struct SolutionsWK {
-    #[iterable]
-    pub solutions: Vec<String>,
+    #[keyed]
+    #[serde(deserialize_with = "deserialize_solutions")]
+    pub solutions: Vec<(Uuid, String)>,
+    #[serde(default)]
+    pub ratings: Vec<SolutionRating>,
 }
 
-impl Completenes for SolutionsWK {
-    fn is_complete(&self) -> bool {
-        self.solutions
+pub const RATING_SCALE: u8 = 5;
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub struct SolutionRating {
+    pub id: Uuid,
+    /// from 1 to `RATING_SCALE`, none until rated
+    #[serde(default, deserialize_with = "deserialize_rating")]
+    pub impact: Option<u8>,
+    #[serde(default, deserialize_with = "deserialize_rating")]
+    pub effort: Option<u8>,
+    #[serde(default, deserialize_with = "deserialize_rating")]
+    pub risk: Option<u8>,
+    #[serde(default, deserialize_with = "deserialize_stakeholder_ratings")]
+    pub stakeholders: Vec<(Uuid, u8)>,
+}
+
+/// reads a rating, values out of the scale are clamped to it
+fn deserialize_rating<'de, D>(deserializer: D) -> Result<Option<u8>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(Option::<u8>::deserialize(deserializer)?.map(|r| r.clamp(1, RATING_SCALE)))
+}
+
+/// reads the ratings per stakeholder, values out of the scale are clamped to it
+fn deserialize_stakeholder_ratings<'de, D>(deserializer: D) -> Result<Vec<(Uuid, u8)>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(Vec::<(Uuid, u8)>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(id, r)| (id, r.clamp(1, RATING_SCALE)))
+        .collect())
+}
+
+impl SolutionRating {
+    pub fn new(id: Uuid) -> Self {
+        Self {
+            id,
+            ..Default::default()
+        }
+    }
+
+    pub fn stakeholder(&self, id: Uuid) -> Option<u8> {
+        self.stakeholders
             .iter()
-            .filter(|r| !r.is_empty())
-            .next()
-            .is_some()
+            .find(|(s, _)| s == &id)
+            .map(|(_, r)| *r)
+    }
+
+    pub fn set_stakeholder(&mut self, id: Uuid, rating: Option<u8>) {
+        self.stakeholders.retain(|(s, _)| s != &id);
+        if let Some(rating) = rating {
+            self.stakeholders.push((id, rating));
+        }
+    }
+
+    pub fn is_rated(&self) -> bool {
+        self.impact.is_some() && self.effort.is_some()
+    }
+
+    /// higher for more impact and stakeholder benefit at less effort and risk,
+    /// none until impact and effort are rated
+    pub fn score(&self) -> Option<i16> {
+        let impact = self.impact? as i16;
+        let effort = self.effort? as i16;
+        let risk = self.risk.unwrap_or_default() as i16;
+        let benefit = self
+            .stakeholders
+            .iter()
+            .map(|(_, r)| *r as i16)
+            .sum::<i16>()
+            / self.stakeholders.len().max(1) as i16;
+        Some(2 * impact + benefit - effort - risk)
+    }
+}
+
+impl SolutionsWK {
+    pub fn rating(&self, id: Uuid) -> SolutionRating {
+        self.ratings
+            .iter()
+            .find(|r| r.id == id)
+            .cloned()
+            .unwrap_or_else(|| SolutionRating::new(id))
+    }
+
+    /// non empty solutions, best rated first, unrated ones keep their order
+    pub fn ranked_solutions(&self) -> Vec<KeyedEntry> {
+        let mut entries = self
+            .solutions
+            .iter()
+            .filter(|(_, s)| !s.is_empty())
+            .cloned()
+            .collect::<Vec<_>>();
+        entries.sort_by_key(|(id, _)| std::cmp::Reverse(self.rating(*id).score()));
+        entries
+    }
+}
+
+impl SolutionsWKFormState {
+    pub fn update_rating(&self, id: Uuid, f: impl FnOnce(&mut SolutionRating)) {
+        self.ratings.update(|ratings| {
+            match ratings.iter_mut().find(|r| r.id == id) {
+                Some(rating) => f(rating),
+                None => {
+                    let mut rating = SolutionRating::new(id);
+                    f(&mut rating);
+                    ratings.push(rating);
+                }
+            };
+        });
+    }
+
+    /// ratings of a stakeholder by solution
+    pub fn stakeholder_ratings(&self, id: Uuid) -> Vec<(Uuid, u8)> {
+        self.ratings
+            .get_untracked()
+            .iter()
+            .filter_map(|r| r.stakeholder(id).map(|v| (r.id, v)))
+            .collect()
+    }
+
+    pub fn forget_rating(&self, id: Uuid) {
+        self.ratings
+            .update(|ratings| ratings.retain(|r| r.id != id));
+    }
+
+    pub fn move_stakeholder_ratings(&self, id: Uuid, to: Option<Uuid>) {
+        self.ratings.update(|ratings| {
+            for rating in ratings.iter_mut() {
+                let value = rating.stakeholder(id);
+                rating.set_stakeholder(id, None);
+                if let Some(to) = to.filter(|to| rating.stakeholder(*to).is_none()) {
+                    rating.set_stakeholder(to, value);
+                }
+            }
+        });
+    }
+}
+
+impl Completenes for SolutionsWK {
+    fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        report.check(
+            ProcessStep::Solution,
+            "solutions.solutions",
+            has_entries(&keyed_values(&self.solutions)),
+            "worksheets.validation.solutions",
+        );
+        report
     }
 }
 
 #[derive(FormState, Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
 pub struct CompromiseWK {
-    pub solution_choices: Vec<String>,
-    pub stakeholder_choices: Vec<String>,
+    #[serde(deserialize_with = "deserialize_solution_choices")]
+    pub solution_choices: Vec<Uuid>,
+    #[serde(deserialize_with = "deserialize_stakeholder_choices")]
+    pub stakeholder_choices: Vec<Uuid>,
     pub question: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChoiceList {
+    Solutions,
+    Stakeholders,
+}
+
+impl CompromiseWKFormState {
+    fn choices(&self, list: ChoiceList) -> &FormState<Vec<Uuid>> {
+        match list {
+            ChoiceList::Solutions => &self.solution_choices,
+            ChoiceList::Stakeholders => &self.stakeholder_choices,
+        }
+    }
+
+    pub fn is_chosen(&self, list: ChoiceList, id: Uuid) -> bool {
+        self.choices(list).get_untracked().contains(&id)
+    }
+
+    pub fn choose(&self, list: ChoiceList, id: Uuid) {
+        if !self.is_chosen(list, id) {
+            self.choices(list).update(|c| c.push(id));
+        }
+    }
+
+    pub fn forget_choice(&self, list: ChoiceList, id: Uuid) {
+        self.choices(list).update(|c| c.retain(|c| c != &id));
+    }
+
+    pub fn replace_choice(&self, list: ChoiceList, id: Uuid, with: Uuid) {
+        self.choices(list).update(|c| {
+            c.iter_mut().filter(|c| **c == id).for_each(|c| *c = with);
+            let mut seen = HashSet::new();
+            c.retain(|c| seen.insert(*c));
+        });
+    }
+}
+
 impl Completenes for CompromiseWK {
-    fn is_complete(&self) -> bool {
-        !self.question.is_empty()
-            && self
-                .solution_choices
-                .iter()
-                .filter(|r| !r.is_empty())
-                .next()
-                .is_some()
-            && self
-                .stakeholder_choices
-                .iter()
-                .filter(|r| !r.is_empty())
-                .next()
-                .is_some()
+    fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        report.check(
+            ProcessStep::Compromise,
+            "compromise.solution_choices",
+            !self.solution_choices.is_empty(),
+            "worksheets.validation.solution_choices",
+        );
+        report.check(
+            ProcessStep::Compromise,
+            "compromise.stakeholder_choices",
+            !self.stakeholder_choices.is_empty(),
+            "worksheets.validation.stakeholder_choices",
+        );
+        report.check(
+            ProcessStep::Compromise,
+            "compromise.question",
+            !self.question.is_empty(),
+            "worksheets.validation.question",
+        );
+        report
     }
 
     fn is_empty(&self) -> bool {
         self.question.is_empty()
-            && self
-                .solution_choices
-                .iter()
-                .filter(|r| !r.is_empty())
-                .next()
-                .is_none()
-            && self
-                .stakeholder_choices
-                .iter()
-                .filter(|r| !r.is_empty())
-                .next()
-                .is_none()
+            && self.solution_choices.is_empty()
+            && self.stakeholder_choices.is_empty()
     }
 }
 
@@ -217,9 +804,21 @@ pub struct ImplementWK {
 }
 
 impl Completenes for ImplementWK {
-    fn is_complete(&self) -> bool {
-        self.now.iter().filter(|r| !r.is_empty()).next().is_some()
-            && self.best.iter().filter(|r| !r.is_empty()).next().is_some()
+    fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        report.check(
+            ProcessStep::Implement,
+            "implement.now",
+            has_entries(&self.now),
+            "worksheets.validation.now",
+        );
+        report.check(
+            ProcessStep::Implement,
+            "implement.best",
+            has_entries(&self.best),
+            "worksheets.validation.best",
+        );
+        report
     }
 
     fn is_empty(&self) -> bool {
@@ -231,25 +830,93 @@ impl Completenes for ImplementWK {
 #[derive(FormState, Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
 pub struct IterateWK {
     pub title: String,
-    pub start_date: String,
-    pub end_date: String,
+    #[serde(default, deserialize_with = "deserialize_date")]
+    pub start_date: Option<NaiveDate>,
+    #[serde(default, deserialize_with = "deserialize_date")]
+    pub end_date: Option<NaiveDate>,
     #[iterable]
     pub resources: Vec<String>,
     #[iterable]
     pub external_resources: Vec<String>,
+    #[serde(default)]
+    pub milestones: Vec<Milestone>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub struct Milestone {
+    pub id: Uuid,
+    pub title: String,
+    #[serde(default, deserialize_with = "deserialize_date")]
+    pub date: Option<NaiveDate>,
+}
+
+impl Milestone {
+    pub fn new() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            ..Default::default()
+        }
+    }
+}
+
+impl IterateWK {
+    pub fn has_valid_dates(&self) -> bool {
+        match (self.start_date, self.end_date) {
+            (Some(start), Some(end)) => end > start,
+            _ => true,
+        }
+    }
+
+    pub fn is_valid_milestone(&self, milestone: &Milestone) -> bool {
+        let within = |date: NaiveDate| {
+            self.start_date.map(|start| date >= start).unwrap_or(true)
+                && self.end_date.map(|end| date <= end).unwrap_or(true)
+        };
+        !milestone.title.is_empty() && milestone.date.map(within).unwrap_or(false)
+    }
 }
 
 impl Completenes for IterateWK {
-    fn is_complete(&self) -> bool {
-        !self.title.is_empty()
-            && !self.start_date.is_empty()
-            && !self.end_date.is_empty()
-            && self
-                .resources
-                .iter()
-                .filter(|r| !r.is_empty())
-                .next()
-                .is_some()
+    fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        report.check(
+            ProcessStep::Iterate,
+            "iterate.title",
+            !self.title.is_empty(),
+            "worksheets.validation.iteration_title",
+        );
+        report.check(
+            ProcessStep::Iterate,
+            "iterate.start_date",
+            self.start_date.is_some(),
+            "worksheets.validation.start_date",
+        );
+        // a single check of the end date, missing or before the start
+        report.check(
+            ProcessStep::Iterate,
+            "iterate.end_date",
+            self.end_date.is_some() && self.has_valid_dates(),
+            if self.end_date.is_some() {
+                "worksheets.validation.date_order"
+            } else {
+                "worksheets.validation.end_date"
+            },
+        );
+        if !self.milestones.is_empty() {
+            report.check(
+                ProcessStep::Iterate,
+                "iterate.milestones",
+                self.milestones.iter().all(|m| self.is_valid_milestone(m)),
+                "worksheets.validation.milestones",
+            );
+        }
+        report.check(
+            ProcessStep::Iterate,
+            "iterate.resources",
+            has_entries(&self.resources),
+            "worksheets.validation.resources",
+        );
+        report
     }
 }
 
@@ -260,6 +927,38 @@ pub struct InquireWK {
     pub personalized: bool,
     #[nested]
     pub contact: Contact,
+    /// follow-up conversation, the first message is the response to the inquery
+    #[serde(default)]
+    pub thread: Vec<ChatMessage>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub struct ChatMessage {
+    pub id: Uuid,
+    /// see `ChatRole`
+    pub role: String,
+    pub text: String,
+}
+
+impl ChatMessage {
+    pub fn new(role: ChatRole, text: String) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            role: role.to_string(),
+            text,
+        }
+    }
+
+    pub fn is_user(&self) -> bool {
+        self.role == ChatRole::User.to_string()
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, EnumString, Display)]
+pub enum ChatRole {
+    #[default]
+    User,
+    Assistant,
 }
 
 #[derive(
@@ -285,21 +984,144 @@ pub enum InqueryOption {
 }
 
 impl Completenes for InquireWK {
-    fn is_complete(&self) -> bool {
-        let inquire = match InqueryOption::from_str(self.inquery_option.as_str()) {
-            Ok(InqueryOption::Custom) => !self.custom_prompt.is_empty(),
-            Ok(_) => true,
-            Err(_) => false,
-        };
-
-        inquire && (!self.personalized || self.contact.is_complete())
+    fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        let option = InqueryOption::from_str(self.inquery_option.as_str());
+        report.check(
+            ProcessStep::Inquire,
+            "inquire.inquery_option",
+            option.is_ok(),
+            "worksheets.validation.inquery_option",
+        );
+        if let Ok(InqueryOption::Custom) = option {
+            report.check(
+                ProcessStep::Inquire,
+                "inquire.custom_prompt",
+                !self.custom_prompt.is_empty(),
+                "worksheets.validation.custom_prompt",
+            );
+        }
+        if self.personalized {
+            report.extend(self.contact.validate());
+        }
+        report
     }
 }
 
-pub trait Completenes {
-    fn is_complete(&self) -> bool;
+#[derive(FormState, Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub struct ReflectWK {
+    #[iterable]
+    pub outcomes: Vec<String>,
+    #[iterable]
+    pub learnings: Vec<String>,
+    /// whether the problem statement still holds, see `StatementReview`
+    pub statement_review: String,
+    pub revised_statement: String,
+}
+
+#[derive(
+    Default,
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    EnumString,
+    VariantArray,
+    Display,
+)]
+pub enum StatementReview {
+    #[default]
+    Holds,
+    Revise,
+}
+
+impl Completenes for ReflectWK {
+    fn validate(&self) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        report.check(
+            ProcessStep::Reflect,
+            "reflect.outcomes",
+            has_entries(&self.outcomes),
+            "worksheets.validation.outcomes",
+        );
+        report.check(
+            ProcessStep::Reflect,
+            "reflect.learnings",
+            has_entries(&self.learnings),
+            "worksheets.validation.learnings",
+        );
+        let review = StatementReview::from_str(self.statement_review.as_str());
+        report.check(
+            ProcessStep::Reflect,
+            "reflect.statement_review",
+            review.is_ok(),
+            "worksheets.validation.statement_review",
+        );
+        if let Ok(StatementReview::Revise) = review {
+            report.check(
+                ProcessStep::Reflect,
+                "reflect.revised_statement",
+                !self.revised_statement.is_empty(),
+                "worksheets.validation.revised_statement",
+            );
+        }
+        report
+    }
 
     fn is_empty(&self) -> bool {
-        !self.is_complete()
+        self.statement_review.is_empty()
+            && self.revised_statement.is_empty()
+            && !has_entries(&self.outcomes)
+            && !has_entries(&self.learnings)
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub struct IterationRecord {
+    pub problem: ProblemWK,
+    pub solutions: SolutionsWK,
+    pub compromise: CompromiseWK,
+    pub implement: ImplementWK,
+    pub iterate: IterateWK,
+    pub reflect: ReflectWK,
+    #[serde(default)]
+    pub custom: Vec<CustomAnswer>,
+}
+
+impl From<IterationRecord> for WorkSheets {
+    fn from(r: IterationRecord) -> Self {
+        WorkSheets {
+            problem: r.problem,
+            solutions: r.solutions,
+            compromise: r.compromise,
+            implement: r.implement,
+            iterate: r.iterate,
+            reflect: r.reflect,
+            inquire: Default::default(),
+            iterations: vec![],
+            template: Default::default(),
+            custom: r.custom,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Hash)]
+pub struct CustomAnswer {
+    pub step: String,
+    pub field: String,
+    /// a single value for text fields
+    pub values: Vec<String>,
+}
+
+impl CustomAnswer {
+    pub fn new(step: &str, field: &str) -> Self {
+        Self {
+            step: step.to_string(),
+            field: field.to_string(),
+            values: vec![],
+        }
     }
 }
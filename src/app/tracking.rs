@@ -1,4 +1,5 @@
 use leptos::*;
+use serde::{Deserialize, Serialize};
 
 use leptos_use::{
     storage::{use_local_storage_with_options, UseStorageOptions},
@@ -68,18 +69,30 @@ pub async fn restore_tracking_session(
 }
 
 #[cfg(feature = "ssr")]
-pub fn complete_inferrence(id: Uuid, result: String) -> Result<(), ServerFnError<String>> {
+pub fn complete_inferrence(
+    id: Uuid,
+    result: String,
+    flags: &[String],
+) -> Result<(), ServerFnError<String>> {
     let conn = Connection::open("default").map_err(safe_error)?;
 
     let sql = r#"
         UPDATE tracking
-        SET inferrence = ?, updated_date = unixepoch()
+        SET inferrence = ?, inferrence_flags = ?, updated_date = unixepoch()
         WHERE id = ?;
 "#;
     _ = conn
         .execute(
             sql,
-            &[Value::Text(result), Value::Blob(Vec::from(id.as_bytes()))],
+            &[
+                Value::Text(result),
+                if flags.is_empty() {
+                    Value::Null
+                } else {
+                    Value::Text(flags.join(","))
+                },
+                Value::Blob(Vec::from(id.as_bytes())),
+            ],
         )
         .map_err(safe_error)?;
 
@@ -182,6 +195,55 @@ CREATE VIEW summary AS
     todo!();
 }
 
+/// ratings of the responses of one prompt variant
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedbackStats {
+    pub inquery_option: String,
+    pub template_version: u32,
+    pub responses: u32,
+    pub helpful: u32,
+    pub not_helpful: u32,
+    pub comments: u32,
+}
+
+/// ratings by inquery option and prompt template version, to compare prompt variants,
+/// only served by debug builds
+#[cfg(debug_assertions)]
+#[server(GetFeedbackStats, "/api")]
+pub async fn get_feedback_stats() -> Result<Vec<FeedbackStats>, ServerFnError<String>> {
+    let conn = Connection::open("default").map_err(safe_error)?;
+
+    let sql = r#"
+    SELECT inquery_option, template_version,
+        COUNT(*) AS responses,
+        COUNT(CASE WHEN rating > 0 THEN 1 END) AS helpful,
+        COUNT(CASE WHEN rating < 0 THEN 1 END) AS not_helpful,
+        COUNT(feedback) AS comments
+    FROM inferrence
+    WHERE inquery_option IS NOT NULL AND done = 1
+    GROUP BY inquery_option, template_version
+    ORDER BY inquery_option, template_version;
+    "#;
+    let data = conn.execute(sql, &[]).map_err(safe_error)?;
+
+    let stats = data
+        .rows()
+        .map(|row| FeedbackStats {
+            inquery_option: row
+                .get::<&str>("inquery_option")
+                .unwrap_or_default()
+                .to_string(),
+            template_version: row.get::<i64>("template_version").unwrap_or_default() as u32,
+            responses: row.get::<i64>("responses").unwrap_or_default() as u32,
+            helpful: row.get::<i64>("helpful").unwrap_or_default() as u32,
+            not_helpful: row.get::<i64>("not_helpful").unwrap_or_default() as u32,
+            comments: row.get::<i64>("comments").unwrap_or_default() as u32,
+        })
+        .collect();
+
+    Ok(stats)
+}
+
 #[derive(Clone, PartialEq, Eq)]
 pub struct SessionId(pub ReadSignal<Option<Uuid>>);
 
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Write};
 
 use super::Language;
 
@@ -35,3 +35,61 @@ pub fn transform_xata_image(url: &str, transform: HashMap<&str, &str>) -> String
 
     format!("{base}.xata.sh/transform/{transform}/{id}")
 }
+
+/// percent-encodes `content` into a `data:` url suitable for `<a download>`
+pub fn data_url(mime: &str, content: &[u8]) -> String {
+    let mut url = String::with_capacity(mime.len() + 6 + content.len() * 3);
+    _ = write!(url, "data:{mime},");
+    for b in content {
+        if b.is_ascii_alphanumeric() || b"-_.~".contains(b) {
+            url.push(*b as char);
+        } else {
+            _ = write!(url, "%{b:02X}");
+        }
+    }
+    url
+}
+
+/// saves `content` as a file by clicking a temporary `<a download>`
+#[cfg_attr(not(feature = "client"), allow(unused_variables))]
+pub fn download_file(file_name: &str, mime: &str, content: &[u8]) {
+    #[cfg(feature = "client")]
+    {
+        use wasm_bindgen::JsCast;
+
+        let link = leptos::document()
+            .create_element("a")
+            .and_then(|a| {
+                a.set_attribute("href", data_url(mime, content).as_str())?;
+                a.set_attribute("download", file_name)?;
+                Ok(a)
+            })
+            .map(|a| a.unchecked_into::<web_sys::HtmlElement>());
+
+        match link {
+            Ok(link) => link.click(),
+            Err(e) => log::error!("download error: {e:?}"),
+        }
+    }
+}
+
+/// writes text to the clipboard via `navigator.clipboard.writeText`
+#[cfg_attr(not(feature = "client"), allow(unused_variables))]
+pub fn copy_to_clipboard(text: &str) {
+    #[cfg(feature = "client")]
+    {
+        use js_sys::{Function, Reflect};
+        use wasm_bindgen::{JsCast, JsValue};
+
+        let navigator = leptos::window().navigator();
+        let write = Reflect::get(&navigator, &JsValue::from_str("clipboard")).and_then(|clip| {
+            let write_text = Reflect::get(&clip, &JsValue::from_str("writeText"))?;
+            let write_text = write_text.dyn_into::<Function>()?;
+            write_text.call1(&clip, &JsValue::from_str(text))
+        });
+
+        if let Err(e) = write {
+            log::error!("clipboard error: {e:?}");
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use std::str::FromStr;
+
+use serde::Deserialize;
+use spin_sdk::{
+    http::{run, send, Method, Request, Response},
+    llm, variables,
+};
+use strum::EnumString;
+
+/// canned responses of the fixture backend
+const FIXTURES: &str = include_str!("../fixtures/inference.json");
+
+/// generation options of a single request
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InferenceParams {
+    pub max_tokens: u32,
+    pub temperature: f32,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct InferenceOutput {
+    pub text: String,
+    pub generated_tokens: u32,
+}
+
+/// completes a prompt, the response continues the text of the prompt
+pub trait InferenceBackend {
+    fn infer(&self, prompt: &str, params: InferenceParams) -> anyhow::Result<InferenceOutput>;
+}
+
+/// backends selectable with the `inference_backend` variable
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, EnumString)]
+#[strum(serialize_all = "snake_case")]
+pub enum BackendKind {
+    #[default]
+    Spin,
+    Openai,
+    Fixture,
+}
+
+/// backend configured for the component
+pub fn inference_backend() -> anyhow::Result<Box<dyn InferenceBackend>> {
+    let kind = variables::get("inference_backend")
+        .ok()
+        .and_then(|kind| BackendKind::from_str(kind.as_str()).ok())
+        .unwrap_or_default();
+
+    Ok(match kind {
+        BackendKind::Spin => Box::new(SpinBackend),
+        BackendKind::Openai => Box::new(OpenAiBackend {
+            url: variables::get("inference_url")?,
+            model: variables::get("inference_model").unwrap_or_default(),
+            api_key: variables::get("inference_key")
+                .ok()
+                .filter(|key| !key.is_empty()),
+        }),
+        BackendKind::Fixture => Box::new(FixtureBackend),
+    })
+}
+
+/// model served by the Spin runtime
+pub struct SpinBackend;
+
+impl InferenceBackend for SpinBackend {
+    fn infer(&self, prompt: &str, params: InferenceParams) -> anyhow::Result<InferenceOutput> {
+        let result = llm::infer_with_options(
+            llm::InferencingModel::Llama2Chat,
+            prompt,
+            llm::InferencingParams {
+                max_tokens: params.max_tokens,
+                repeat_penalty: 1.1,
+                repeat_penalty_last_n_token_count: 64,
+                temperature: params.temperature,
+                top_k: 40,
+                top_p: 0.82,
+            },
+        )?;
+
+        Ok(InferenceOutput {
+            text: result.text,
+            generated_tokens: result.usage.generated_token_count,
+        })
+    }
+}
+
+/// completions endpoint of an OpenAI compatible server, e.g. llama.cpp
+pub struct OpenAiBackend {
+    /// base url, e.g. http://localhost:8080/v1
+    pub url: String,
+    pub model: String,
+    pub api_key: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CompletionResponse {
+    choices: Vec<CompletionChoice>,
+    #[serde(default)]
+    usage: Option<CompletionUsage>,
+}
+
+#[derive(Deserialize)]
+struct CompletionChoice {
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct CompletionUsage {
+    completion_tokens: u32,
+}
+
+impl InferenceBackend for OpenAiBackend {
+    fn infer(&self, prompt: &str, params: InferenceParams) -> anyhow::Result<InferenceOutput> {
+        let body = serde_json::json!({
+            "model": self.model,
+            "prompt": prompt,
+            "max_tokens": params.max_tokens,
+            "temperature": params.temperature,
+            "top_p": 0.82,
+            "frequency_penalty": 0.1,
+        });
+
+        let mut req = Request::builder();
+        req.method(Method::Post)
+            .uri(format!("{}/completions", self.url.trim_end_matches('/')))
+            .header("Content-Type", "application/json")
+            .body(body.to_string());
+        if let Some(key) = self.api_key.as_ref() {
+            req.header("Authorization", format!("Bearer {key}"));
+        }
+
+        let res = run(async move {
+            let res: Response = send(req).await?;
+            if *res.status() >= 400 {
+                anyhow::bail!("inference endpoint responded with {}", res.status());
+            }
+            Ok(res.into_body())
+        })?;
+
+        let completion: CompletionResponse = serde_json::from_slice(&res)?;
+        let text = completion
+            .choices
+            .into_iter()
+            .next()
+            .map(|c| c.text)
+            .unwrap_or_default();
+        // servers without usage report count words instead
+        let generated_tokens = completion
+            .usage
+            .map(|u| u.completion_tokens)
+            .unwrap_or_else(|| text.split_whitespace().count() as u32);
+
+        Ok(InferenceOutput {
+            text,
+            generated_tokens,
+        })
+    }
+}
+
+/// deterministic responses to develop and test prompts without a model
+///
+/// the first fixture contained in the prompt is streamed word by word,
+/// a word counts as a token
+pub struct FixtureBackend;
+
+#[derive(Deserialize)]
+struct Fixture {
+    contains: String,
+    response: String,
+}
+
+impl InferenceBackend for FixtureBackend {
+    fn infer(&self, prompt: &str, params: InferenceParams) -> anyhow::Result<InferenceOutput> {
+        let fixtures: Vec<Fixture> = serde_json::from_str(FIXTURES)?;
+        let response = fixtures
+            .into_iter()
+            .find(|f| prompt.contains(f.contains.as_str()))
+            .map(|f| f.response)
+            .unwrap_or_default();
+
+        // the prompt of a continued inference ends with the response so far
+        let words = response.split_whitespace().collect::<Vec<_>>();
+        let skip = (0..=words.len())
+            .rev()
+            .find(|n| prompt.trim_end().ends_with(words[..*n].join(" ").as_str()))
+            .unwrap_or_default();
+
+        let next = words
+            .iter()
+            .skip(skip)
+            .take(params.max_tokens as usize)
+            .copied()
+            .collect::<Vec<_>>();
+
+        Ok(InferenceOutput {
+            text: next.iter().map(|w| format!("{w} ")).collect(),
+            generated_tokens: next.len() as u32,
+        })
+    }
+}
@@ -5,6 +5,8 @@ extern crate form_signal_macro;
 
 pub mod app;
 
+#[cfg(feature = "ssr")]
+pub mod inference;
 #[cfg(feature = "ssr")]
 pub mod server;
 
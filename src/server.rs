@@ -22,13 +22,27 @@ async fn handle_a_nvlkv_xyz(req: IncomingRequest, resp_out: ResponseOutparam) {
     register_explicit::<crate::app::projects::GetProjects>();
     register_explicit::<crate::app::projects::GetProjectDetails>();
     register_explicit::<crate::app::process::InquireInferrence>();
+    register_explicit::<crate::app::process::InquireFollowUp>();
+    register_explicit::<crate::app::process::InquireInferrenceChunk>();
+    register_explicit::<crate::app::process::StopInferrence>();
+    register_explicit::<crate::app::process::PreviewInferrencePrompt>();
+    register_explicit::<crate::app::process::SuggestEntries>();
+    register_explicit::<crate::app::process::SuggestEdits>();
+    register_explicit::<crate::app::process::RateInferrence>();
     register_explicit::<crate::app::process::InquirePersonal>();
     register_explicit::<crate::app::process::InquireContact>();
+    register_explicit::<crate::app::process::ShareWorksheets>();
+    register_explicit::<crate::app::process::GetSharedWorksheets>();
+    register_explicit::<crate::app::process::RevokeSharedWorksheets>();
+    register_explicit::<crate::app::process::CreateCollabSession>();
+    register_explicit::<crate::app::process::SyncCollabSession>();
     register_explicit::<crate::app::resume::GetCvEntries>();
     register_explicit::<crate::app::pages::GetLinks>();
     register_explicit::<crate::app::tracking::NewSession>();
     register_explicit::<crate::app::tracking::RestoreSession>();
     register_explicit::<crate::app::tracking::WkDownloadSession>();
+    #[cfg(debug_assertions)]
+    register_explicit::<crate::app::tracking::GetFeedbackStats>();
 
     let app = crate::app::App;
 
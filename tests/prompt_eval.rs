@@ -0,0 +1,239 @@
+//! Offline evaluation of the inquery prompts.
+//!
+//! Every workbook in `fixtures/workbooks` is inquired with every [InqueryOption], the prompt
+//! and the response are written to `fixtures/prompt_eval/<workbook>/<option>.md`. Commit the
+//! reports with a change of the prompts to review its effect as a diff.
+//!
+//! ```bash
+//! cargo test --features ssr --test prompt_eval -- --ignored
+//! ```
+//!
+//! Responses come from `fixtures/inference.json` unless `PROMPT_EVAL_URL` points to a local
+//! OpenAI compatible server, e.g. `http://localhost:8080/v1` of llama.cpp.
+
+use std::{
+    fs,
+    io::{Read, Write},
+    net::TcpStream,
+    path::{Path, PathBuf},
+};
+
+use a_nvlkv_xyz::{
+    app::{
+        process::{filter_output, inferrence_prompt},
+        state::{InqueryOption, WorkSheets},
+        Language,
+    },
+    inference::{FixtureBackend, InferenceBackend, InferenceOutput, InferenceParams},
+};
+use strum::VariantArray;
+
+const WORKBOOKS_DIR: &str = "fixtures/workbooks";
+const REPORTS_DIR: &str = "fixtures/prompt_eval";
+
+/// completions endpoint of a local server, the Spin http client is unavailable outside of Spin
+struct LocalBackend {
+    /// host and port, e.g. localhost:8080
+    authority: String,
+    /// path of the completions, e.g. /v1/completions
+    path: String,
+    model: String,
+}
+
+impl LocalBackend {
+    fn from_url(url: &str) -> anyhow::Result<Self> {
+        let url = url
+            .strip_prefix("http://")
+            .ok_or_else(|| anyhow::anyhow!("only plain http is supported: {url}"))?;
+        let (authority, base) = url.split_once('/').unwrap_or((url, ""));
+        let base = base.trim_end_matches('/');
+
+        Ok(Self {
+            authority: authority.to_string(),
+            path: if base.is_empty() {
+                "/completions".to_string()
+            } else {
+                format!("/{base}/completions")
+            },
+            model: std::env::var("PROMPT_EVAL_MODEL").unwrap_or_default(),
+        })
+    }
+}
+
+impl InferenceBackend for LocalBackend {
+    fn infer(&self, prompt: &str, params: InferenceParams) -> anyhow::Result<InferenceOutput> {
+        let body = serde_json::json!({
+            "model": self.model,
+            "prompt": prompt,
+            "max_tokens": params.max_tokens,
+            "temperature": params.temperature,
+            "top_p": 0.82,
+            "frequency_penalty": 0.1,
+        })
+        .to_string();
+
+        let mut stream = TcpStream::connect(self.authority.as_str())?;
+        write!(
+            stream,
+            "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            self.path,
+            self.authority,
+            body.len(),
+        )?;
+
+        let mut res = String::new();
+        stream.read_to_string(&mut res)?;
+        let (head, body) = res
+            .split_once("\r\n\r\n")
+            .ok_or_else(|| anyhow::anyhow!("malformed response"))?;
+        if !head.starts_with("HTTP/1.1 200") {
+            anyhow::bail!(
+                "inference endpoint responded with {}",
+                head.lines().next().unwrap_or_default()
+            );
+        }
+
+        let chunked = head.lines().any(|line| {
+            line.split_once(':').is_some_and(|(name, value)| {
+                name.trim().eq_ignore_ascii_case("transfer-encoding")
+                    && value.trim().eq_ignore_ascii_case("chunked")
+            })
+        });
+        let body = if chunked {
+            dechunk(body)?
+        } else {
+            body.to_string()
+        };
+
+        let completion: serde_json::Value = serde_json::from_str(body.as_str())?;
+        let text = completion["choices"][0]["text"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        let generated_tokens = completion["usage"]["completion_tokens"]
+            .as_u64()
+            .map(|t| t as u32)
+            .unwrap_or_else(|| text.split_whitespace().count() as u32);
+
+        Ok(InferenceOutput {
+            text,
+            generated_tokens,
+        })
+    }
+}
+
+/// joins the chunks of a body sent with `Transfer-Encoding: chunked`
+fn dechunk(mut body: &str) -> anyhow::Result<String> {
+    let mut joined = String::new();
+    loop {
+        let (size, rest) = body
+            .split_once("\r\n")
+            .ok_or_else(|| anyhow::anyhow!("malformed chunk"))?;
+        // chunk extensions follow the size after a semicolon
+        let size = size.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size, 16)?;
+        if size == 0 {
+            return Ok(joined);
+        }
+        let chunk = rest
+            .get(..size)
+            .ok_or_else(|| anyhow::anyhow!("truncated chunk"))?;
+        joined.push_str(chunk);
+        body = rest[size..].trim_start_matches("\r\n");
+    }
+}
+
+fn backend() -> Box<dyn InferenceBackend> {
+    match std::env::var("PROMPT_EVAL_URL") {
+        Ok(url) => Box::new(LocalBackend::from_url(url.as_str()).unwrap()),
+        Err(_) => Box::new(FixtureBackend),
+    }
+}
+
+fn workbooks() -> Vec<(String, WorkSheets)> {
+    let mut paths = fs::read_dir(WORKBOOKS_DIR)
+        .unwrap()
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect::<Vec<_>>();
+    paths.sort();
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let name = path.file_stem().unwrap().to_string_lossy().to_string();
+            let wk = serde_json::from_str(fs::read_to_string(&path).unwrap().as_str())
+                .unwrap_or_else(|e| panic!("invalid workbook {}: {e}", path.display()));
+            (name, wk)
+        })
+        .collect()
+}
+
+/// prompt and response of one option, or the error the inquery failed with
+fn report(backend: &dyn InferenceBackend, wk: &WorkSheets, option: InqueryOption) -> String {
+    let mut wk = wk.clone();
+    wk.inquire.inquery_option = option.to_string();
+
+    let mut report = format!("# {option}\n\n");
+
+    let (prompt, max_tokens, temperature, trimmed) = match inferrence_prompt(wk, Language::En) {
+        Ok(prompt) => prompt,
+        Err(e) => {
+            report.push_str(format!("Rejected: {e}\n").as_str());
+            return report;
+        }
+    };
+    report.push_str(
+        format!(
+            "max_tokens: {max_tokens}, temperature: {temperature}, trimmed: [{}]\n\n",
+            trimmed.join(", ")
+        )
+        .as_str(),
+    );
+    report.push_str(format!("## Prompt\n\n```\n{prompt}```\n\n").as_str());
+
+    match backend.infer(
+        prompt.as_str(),
+        InferenceParams {
+            max_tokens,
+            temperature,
+        },
+    ) {
+        Ok(output) => {
+            let filtered = filter_output(prompt.as_str(), output.text.as_str());
+            report.push_str(
+                format!(
+                    "## Response\n\ngenerated_tokens: {}, flags: [{}]\n\n```\n{}\n```\n",
+                    output.generated_tokens,
+                    filtered.flags.join(", "),
+                    filtered.text.trim(),
+                )
+                .as_str(),
+            );
+        }
+        Err(e) => report.push_str(format!("## Response\n\nFailed: {e}\n").as_str()),
+    }
+
+    report
+}
+
+fn write_report(dir: &Path, option: InqueryOption, report: String) {
+    fs::create_dir_all(dir).unwrap();
+    fs::write(dir.join(format!("{option}.md")), report).unwrap();
+}
+
+#[test]
+#[ignore = "writes reports, run when changing the prompts"]
+fn prompt_eval() {
+    let backend = backend();
+    let workbooks = workbooks();
+    assert!(!workbooks.is_empty(), "no workbooks in {WORKBOOKS_DIR}");
+
+    for (name, wk) in workbooks {
+        let dir = PathBuf::from(REPORTS_DIR).join(name.as_str());
+        for option in InqueryOption::VARIANTS {
+            println!("{name} {option}");
+            write_report(&dir, *option, report(backend.as_ref(), &wk, *option));
+        }
+    }
+}